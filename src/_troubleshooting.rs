@@ -16,9 +16,11 @@
 //!
 //! ### Backlight pin
 //!
-//! This driver does **NOT** handle the backlight pin to keep the code simpler.
-//! Users must control the backlight manually. First thing to try is to see if
-//! setting the backlight pin to high fixes the issue.
+//! The backlight is only controlled by this driver if it was passed to the
+//! builder via the `backlight` method. Otherwise users must control the
+//! backlight manually. First thing to try is to see if setting the backlight
+//! pin to high fixes the issue. Some modules use an active low backlight pin,
+//! which can be configured with `BacklightPin::new_active_low`.
 //!
 //! ### Transport misconfiguration (e.g. SPI)
 //!
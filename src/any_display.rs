@@ -0,0 +1,597 @@
+//! [AnyDisplay] for controllers detected at runtime.
+
+use embedded_graphics_core::{pixelcolor::Rgb565, primitives::Rectangle};
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::{delay::DelayNs, digital::Wait};
+
+use crate::{
+    backlight::{Backlight, NoBacklight},
+    dcs,
+    interface::{Interface, PixelWord, ReadInterface},
+    models::{
+        AnyModel, ILI9341Rgb565, ILI9342CRgb565, ILI9486Rgb565, ILI9488Rgb565, ST7735s, GC9107,
+        GC9A01, ST7789, ST7796,
+    },
+    options, Display, DisplayError, DisplayState, NoTePin, SleepError, SleepingDisplay, VsyncError,
+};
+
+/// Display driver for a controller model detected at runtime.
+///
+/// Created by [`Builder::init_detect`](crate::Builder::init_detect). Each variant wraps a
+/// [`Display`] for the matching [`AnyModel`], the methods of this type are dispatched to it.
+/// Match on the variants to access model specific functionality.
+///
+/// Like [`Display`], the tearing effect pin `TE` and the backlight `BL` are optional.
+pub enum AnyDisplay<DI, RST, TE = NoTePin, BL = NoBacklight>
+where
+    DI: Interface,
+    RST: OutputPin,
+{
+    /// [`GC9107`] display
+    GC9107(Display<DI, GC9107, RST, TE, BL>),
+    /// [`GC9A01`] display
+    GC9A01(Display<DI, GC9A01, RST, TE, BL>),
+    /// [`ILI9341Rgb565`] display
+    ILI9341Rgb565(Display<DI, ILI9341Rgb565, RST, TE, BL>),
+    /// [`ILI9342CRgb565`] display
+    ILI9342CRgb565(Display<DI, ILI9342CRgb565, RST, TE, BL>),
+    /// [`ILI9486Rgb565`] display
+    ILI9486Rgb565(Display<DI, ILI9486Rgb565, RST, TE, BL>),
+    /// [`ILI9488Rgb565`] display
+    ILI9488Rgb565(Display<DI, ILI9488Rgb565, RST, TE, BL>),
+    /// [`ST7735s`] display
+    ST7735s(Display<DI, ST7735s, RST, TE, BL>),
+    /// [`ST7789`] display
+    ST7789(Display<DI, ST7789, RST, TE, BL>),
+    /// [`ST7796`] display
+    ST7796(Display<DI, ST7796, RST, TE, BL>),
+}
+
+macro_rules! dispatch {
+    ($self:expr, $display:ident => $body:expr) => {
+        match $self {
+            AnyDisplay::GC9107($display) => $body,
+            AnyDisplay::GC9A01($display) => $body,
+            AnyDisplay::ILI9341Rgb565($display) => $body,
+            AnyDisplay::ILI9342CRgb565($display) => $body,
+            AnyDisplay::ILI9486Rgb565($display) => $body,
+            AnyDisplay::ILI9488Rgb565($display) => $body,
+            AnyDisplay::ST7735s($display) => $body,
+            AnyDisplay::ST7789($display) => $body,
+            AnyDisplay::ST7796($display) => $body,
+        }
+    };
+}
+
+impl<DI, RST, TE, BL> AnyDisplay<DI, RST, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin,
+{
+    /// Returns the model of the display.
+    pub fn model(&self) -> AnyModel {
+        match self {
+            Self::GC9107(_) => AnyModel::GC9107,
+            Self::GC9A01(_) => AnyModel::GC9A01,
+            Self::ILI9341Rgb565(_) => AnyModel::ILI9341Rgb565,
+            Self::ILI9342CRgb565(_) => AnyModel::ILI9342CRgb565,
+            Self::ILI9486Rgb565(_) => AnyModel::ILI9486Rgb565,
+            Self::ILI9488Rgb565(_) => AnyModel::ILI9488Rgb565,
+            Self::ST7735s(_) => AnyModel::ST7735s,
+            Self::ST7789(_) => AnyModel::ST7789,
+            Self::ST7796(_) => AnyModel::ST7796,
+        }
+    }
+
+    /// See [`Display::orientation`].
+    pub fn orientation(&self) -> options::Orientation {
+        dispatch!(self, d => d.orientation())
+    }
+
+    /// See [`Display::set_orientation`].
+    pub async fn set_orientation(
+        &mut self,
+        orientation: options::Orientation,
+    ) -> Result<(), DI::Error> {
+        dispatch!(self, d => d.set_orientation(orientation).await)
+    }
+
+    /// See [`Display::show_raw_data`].
+    pub async fn show_raw_data<DW>(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        pixel_data: &[DW],
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface<Word = DW>,
+        DW: Copy,
+    {
+        dispatch!(self, d => d.show_raw_data(x, y, width, height, pixel_data).await)
+    }
+
+    /// See [`Display::fill_rect`].
+    pub async fn fill_rect(&mut self, area: Rectangle, color: Rgb565) -> Result<(), DI::Error>
+    where
+        DI::Word: PixelWord,
+    {
+        dispatch!(self, d => d.fill_rect(area, color).await)
+    }
+
+    /// See [`Display::clear`].
+    pub async fn clear(&mut self, color: Rgb565) -> Result<(), DI::Error>
+    where
+        DI::Word: PixelWord,
+    {
+        dispatch!(self, d => d.clear(color).await)
+    }
+
+    /// See [`Display::set_vertical_scroll_region`].
+    pub async fn set_vertical_scroll_region(
+        &mut self,
+        top_fixed_area: u16,
+        bottom_fixed_area: u16,
+    ) -> Result<(), DI::Error> {
+        dispatch!(self, d => d.set_vertical_scroll_region(top_fixed_area, bottom_fixed_area).await)
+    }
+
+    /// See [`Display::set_vertical_scroll_offset`].
+    pub async fn set_vertical_scroll_offset(&mut self, offset: u16) -> Result<(), DI::Error> {
+        dispatch!(self, d => d.set_vertical_scroll_offset(offset).await)
+    }
+
+    /// See [`Display::set_tearing_effect`].
+    pub async fn set_tearing_effect(
+        &mut self,
+        tearing_effect: options::TearingEffect,
+    ) -> Result<(), DI::Error> {
+        dispatch!(self, d => d.set_tearing_effect(tearing_effect).await)
+    }
+
+    /// See [`Display::set_tear_scanline`].
+    pub async fn set_tear_scanline(&mut self, scanline: u16) -> Result<(), DI::Error> {
+        dispatch!(self, d => d.set_tear_scanline(scanline).await)
+    }
+
+    /// See [`Display::read_scanline`].
+    pub async fn read_scanline(&mut self) -> Result<u16, DI::Error>
+    where
+        DI: ReadInterface,
+    {
+        dispatch!(self, d => d.read_scanline().await)
+    }
+
+    /// See [`Display::set_brightness`].
+    pub async fn set_brightness(&mut self, brightness: u8) -> Result<(), DI::Error> {
+        dispatch!(self, d => d.set_brightness(brightness).await)
+    }
+
+    /// See [`Display::set_cabc_mode`].
+    pub async fn set_cabc_mode(&mut self, mode: options::CabcMode) -> Result<(), DI::Error> {
+        dispatch!(self, d => d.set_cabc_mode(mode).await)
+    }
+
+    /// See [`Display::set_gamma`].
+    pub async fn set_gamma(
+        &mut self,
+        gamma: options::GammaCurve,
+    ) -> Result<(), DisplayError<DI::Error>> {
+        dispatch!(self, d => d.set_gamma(gamma).await)
+    }
+
+    /// See [`Display::set_frame_rate`].
+    pub async fn set_frame_rate(
+        &mut self,
+        frame_rate: options::FrameRate,
+    ) -> Result<(), DisplayError<DI::Error>> {
+        dispatch!(self, d => d.set_frame_rate(frame_rate).await)
+    }
+
+    /// See [`Display::enter_partial_mode`].
+    pub async fn enter_partial_mode(
+        &mut self,
+        rows: core::ops::Range<u16>,
+    ) -> Result<(), DisplayError<DI::Error>> {
+        dispatch!(self, d => d.enter_partial_mode(rows).await)
+    }
+
+    /// See [`Display::exit_partial_mode`].
+    pub async fn exit_partial_mode(&mut self) -> Result<(), DI::Error> {
+        dispatch!(self, d => d.exit_partial_mode().await)
+    }
+
+    /// See [`Display::read_id`].
+    pub async fn read_id(&mut self) -> Result<dcs::DisplayId, DI::Error>
+    where
+        DI: ReadInterface,
+    {
+        dispatch!(self, d => d.read_id().await)
+    }
+
+    /// See [`Display::read_status`].
+    pub async fn read_status(&mut self) -> Result<dcs::DisplayStatus, DI::Error>
+    where
+        DI: ReadInterface,
+    {
+        dispatch!(self, d => d.read_status().await)
+    }
+
+    /// See [`Display::sleep`].
+    pub async fn sleep<D: DelayNs>(
+        mut self,
+        delay: &mut D,
+    ) -> Result<SleepingDisplay<Self>, SleepError<Self, DisplayError<DI::Error, BL::Error>>>
+    where
+        BL: Backlight,
+    {
+        match dispatch!(&mut self, d => d.enter_sleep(delay).await) {
+            Ok(()) => Ok(SleepingDisplay { display: self }),
+            Err(error) => Err(SleepError {
+                display: self,
+                error,
+            }),
+        }
+    }
+}
+
+impl<DI, RST, TE, BL> AnyDisplay<DI, RST, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// See [`Display::set_backlight`].
+    pub fn set_backlight(&mut self, level: u8) -> Result<(), BL::Error> {
+        dispatch!(self, d => d.set_backlight(level))
+    }
+
+    /// See [`Display::backlight`].
+    pub fn backlight(&self) -> u8 {
+        dispatch!(self, d => d.backlight())
+    }
+
+    /// See [`Display::release_backlight`].
+    pub fn release_backlight(self) -> (AnyDisplay<DI, RST, TE>, BL) {
+        macro_rules! release_backlight {
+            ($($model:ident),*) => {
+                match self {
+                    $(Self::$model(d) => {
+                        let (d, backlight) = d.release_backlight();
+                        (AnyDisplay::$model(d), backlight)
+                    })*
+                }
+            };
+        }
+
+        release_backlight!(
+            GC9107,
+            GC9A01,
+            ILI9341Rgb565,
+            ILI9342CRgb565,
+            ILI9486Rgb565,
+            ILI9488Rgb565,
+            ST7735s,
+            ST7789,
+            ST7796
+        )
+    }
+}
+
+impl<DI, RST, TE, BL> AnyDisplay<DI, RST, TE, BL>
+where
+    DI: Interface,
+    RST: OutputPin,
+    TE: Wait,
+{
+    /// See [`Display::wait_for_vsync`].
+    pub async fn wait_for_vsync<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Result<(), VsyncError<DI::Error, TE::Error>> {
+        dispatch!(self, d => d.wait_for_vsync(delay, timeout_us).await)
+    }
+
+    /// See [`Display::show_raw_data_vsync`].
+    #[allow(clippy::too_many_arguments)]
+    pub async fn show_raw_data_vsync<DW, D>(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        pixel_data: &[DW],
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Result<(), VsyncError<DI::Error, TE::Error>>
+    where
+        DI: Interface<Word = DW>,
+        DW: Copy,
+        D: DelayNs,
+    {
+        dispatch!(self, d => {
+            d.show_raw_data_vsync(x, y, width, height, pixel_data, delay, timeout_us)
+                .await
+        })
+    }
+}
+
+impl<DI, RST, TE> AnyDisplay<DI, RST, TE>
+where
+    DI: Interface,
+    RST: OutputPin,
+    TE: Wait,
+{
+    ///
+    /// Release resources allocated to this driver back.
+    /// This returns the display interface, reset pin, the detected model and the tearing
+    /// effect pin.
+    ///
+    /// A display with a backlight needs to be released with
+    /// [`release_backlight`](AnyDisplay::release_backlight) first.
+    ///
+    pub fn release(self) -> (DI, AnyModel, Option<RST>, TE) {
+        let model = self.model();
+        let (di, rst, te) = dispatch!(self, d => {
+            let (di, _, rst, te) = d.release();
+            (di, rst, te)
+        });
+        (di, model, rst, te)
+    }
+}
+
+impl<DI, RST> AnyDisplay<DI, RST>
+where
+    DI: Interface,
+    RST: OutputPin,
+{
+    ///
+    /// Release resources allocated to this driver back.
+    /// This returns the display interface, reset pin and the detected model.
+    ///
+    /// A display with a backlight needs to be released with
+    /// [`release_backlight`](AnyDisplay::release_backlight) first.
+    ///
+    pub fn release(self) -> (DI, AnyModel, Option<RST>) {
+        let model = self.model();
+        let (di, rst) = dispatch!(self, d => {
+            let (di, _, rst) = d.release();
+            (di, rst)
+        });
+        (di, model, rst)
+    }
+}
+
+impl<DI, RST, TE, BL> SleepingDisplay<AnyDisplay<DI, RST, TE, BL>>
+where
+    DI: Interface,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// See [`SleepingDisplay::wake`].
+    pub async fn wake<D: DelayNs>(
+        mut self,
+        delay: &mut D,
+    ) -> Result<AnyDisplay<DI, RST, TE, BL>, SleepError<Self, DisplayError<DI::Error, BL::Error>>>
+    {
+        match dispatch!(&mut self.display, d => d.exit_sleep(delay).await) {
+            Ok(()) => Ok(self.display),
+            Err(error) => Err(SleepError {
+                display: self,
+                error,
+            }),
+        }
+    }
+}
+
+impl<DI, RST, TE, BL> DisplayState<AnyDisplay<DI, RST, TE, BL>>
+where
+    DI: Interface,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// See [`DisplayState::sleep`].
+    pub async fn sleep<D: DelayNs>(
+        self,
+        delay: &mut D,
+    ) -> Result<Self, SleepError<Self, DisplayError<DI::Error, BL::Error>>> {
+        match self {
+            Self::Awake(display) => display
+                .sleep(delay)
+                .await
+                .map(Self::Sleeping)
+                .map_err(SleepError::map_display(Self::Awake)),
+            Self::Sleeping(_) => Ok(self),
+        }
+    }
+
+    /// See [`DisplayState::wake`].
+    pub async fn wake<D: DelayNs>(
+        self,
+        delay: &mut D,
+    ) -> Result<Self, SleepError<Self, DisplayError<DI::Error, BL::Error>>> {
+        match self {
+            Self::Awake(_) => Ok(self),
+            Self::Sleeping(display) => display
+                .wake(delay)
+                .await
+                .map(Self::Awake)
+                .map_err(SleepError::map_display(Self::Sleeping)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::convert::Infallible;
+
+    use crate::{
+        _mock::{MockDelay, MockOutputPin},
+        interface::{DummyCycles, InterfaceKind},
+        models::ILI9341Rgb565,
+        Builder, NoResetPin,
+    };
+
+    use super::*;
+
+    /// Interface which returns fixed RDDID and ID4 responses.
+    struct IdInterface {
+        rddid: [u8; 3],
+        id4: [u8; 3],
+        soft_resets: usize,
+    }
+
+    impl Interface for IdInterface {
+        type Word = u8;
+        type Error = Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+        async fn send_command(&mut self, command: u8, _args: &[u8]) -> Result<(), Self::Error> {
+            if command == 0x01 {
+                self.soft_resets += 1;
+            }
+            Ok(())
+        }
+
+        async fn send_data_slice(&mut self, _data: &[Self::Word]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl ReadInterface for IdInterface {
+        async fn read_command(
+            &mut self,
+            command: u8,
+            dummy: DummyCycles,
+            buffer: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            match (command, dummy) {
+                (0x04, DummyCycles::Clock) => buffer.copy_from_slice(&self.rddid),
+                (0xD3, DummyCycles::Byte) => buffer.copy_from_slice(&self.id4),
+                _ => buffer.fill(0),
+            }
+            Ok(())
+        }
+    }
+
+    async fn detect(rddid: [u8; 3], id4: [u8; 3]) -> AnyDisplay<IdInterface, NoResetPin> {
+        let di = IdInterface {
+            rddid,
+            id4,
+            soft_resets: 0,
+        };
+        Builder::new(ILI9341Rgb565, di)
+            .init_detect(&mut MockDelay)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn detects_model_from_display_id() {
+        tokio_test::block_on(async {
+            let display = detect([0x85, 0x85, 0x52], [0; 3]).await;
+            assert_eq!(display.model(), AnyModel::ST7789);
+        });
+    }
+
+    #[test]
+    fn detects_model_from_id4() {
+        tokio_test::block_on(async {
+            let display = detect([0; 3], [0x00, 0x77, 0x96]).await;
+            assert_eq!(display.model(), AnyModel::ST7796);
+        });
+    }
+
+    #[test]
+    fn detection_resets_once() {
+        tokio_test::block_on(async {
+            let display = detect([0x85, 0x85, 0x52], [0; 3]).await;
+            let AnyDisplay::ST7789(display) = display else {
+                panic!("expected ST7789");
+            };
+            assert_eq!(display.di.soft_resets, 1);
+        });
+    }
+
+    #[derive(Default)]
+    struct TestBacklight(Vec<u8>);
+
+    impl Backlight for TestBacklight {
+        type Error = Infallible;
+
+        fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+            self.0.push(level);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn detection_keeps_backlight() {
+        tokio_test::block_on(async {
+            let di = IdInterface {
+                rddid: [0x85, 0x85, 0x52],
+                id4: [0; 3],
+                soft_resets: 0,
+            };
+            let display = Builder::new(ILI9341Rgb565, di)
+                .backlight(TestBacklight::default())
+                .backlight_level(200)
+                .init_detect(&mut MockDelay)
+                .await
+                .unwrap();
+            assert_eq!(display.backlight(), 200);
+
+            let (display, backlight) = display.release_backlight();
+            assert_eq!(backlight.0, [0, 200]);
+            let (_, model, _) = display.release();
+            assert_eq!(model, AnyModel::ST7789);
+        });
+    }
+
+    #[test]
+    fn falls_back_to_builder_model() {
+        tokio_test::block_on(async {
+            let display = detect([0; 3], [0; 3]).await;
+            assert_eq!(display.model(), AnyModel::ILI9341Rgb565);
+        });
+    }
+
+    #[test]
+    fn default_display_size_follows_detected_model() {
+        tokio_test::block_on(async {
+            let display = detect([0x00, 0x9A, 0x01], [0; 3]).await;
+            let AnyDisplay::GC9A01(display) = display else {
+                panic!("expected GC9A01");
+            };
+            assert_eq!(display.options.display_size, (240, 240));
+        });
+    }
+
+    #[test]
+    fn explicit_display_size_is_kept() {
+        tokio_test::block_on(async {
+            let display = Builder::new(
+                ILI9341Rgb565,
+                IdInterface {
+                    rddid: [0x85, 0x85, 0x52],
+                    id4: [0; 3],
+                    soft_resets: 0,
+                },
+            )
+            .reset_pin(MockOutputPin)
+            .display_size(240, 240)
+            .init_detect(&mut MockDelay)
+            .await
+            .unwrap();
+
+            let AnyDisplay::ST7789(display) = display else {
+                panic!("expected ST7789");
+            };
+            assert_eq!(display.options.display_size, (240, 240));
+        });
+    }
+}
@@ -0,0 +1,198 @@
+//! Backlight control.
+//!
+//! Most LCD modules have a separate pin to control the backlight. If a backlight is passed to
+//! [`Builder::backlight`](crate::Builder::backlight), it is kept off while the display is
+//! initialized, to hide the random contents of the frame memory, and turned on once
+//! [`Builder::init`](crate::Builder::init) has completed. The backlight is also turned off
+//! while the display is sleeping.
+//!
+//! Two implementations of the [`Backlight`] trait are provided:
+//!
+//! - [`BacklightPin`] for backlights which can only be turned on and off with an
+//!   [`OutputPin`].
+//! - [`BacklightPwm`] for dimmable backlights which are driven by a PWM channel implementing
+//!   [`SetDutyCycle`].
+//!
+//! # Example
+//!
+//! ```
+//! use lcd_async::{backlight::BacklightPin, models::ILI9341Rgb565, Builder};
+//!
+//! # tokio_test::block_on(async {
+//! # let di = lcd_async::_mock::MockDisplayInterface;
+//! # let bl = lcd_async::_mock::MockOutputPin;
+//! # let mut delay = lcd_async::_mock::MockDelay;
+//! let display = Builder::new(ILI9341Rgb565, di)
+//!     .backlight(BacklightPin::new(bl))
+//!     .init(&mut delay)
+//!     .await
+//!     .unwrap();
+//!
+//! // the backlight is turned off while sleeping and restored by `wake`
+//! let sleeping = display.sleep(&mut delay).await.unwrap();
+//! let display = sleeping.wake(&mut delay).await.unwrap();
+//! # });
+//! ```
+
+use core::convert::Infallible;
+
+use embedded_hal::{digital::OutputPin, pwm::SetDutyCycle};
+
+/// Backlight brightness control.
+pub trait Backlight {
+    /// Error type
+    type Error: core::fmt::Debug;
+
+    /// Sets the backlight brightness.
+    ///
+    /// The level ranges from `0` (off) to `255` (full brightness). Backlights which can't be
+    /// dimmed are turned on for all levels except `0`.
+    fn set_level(&mut self, level: u8) -> Result<(), Self::Error>;
+}
+
+/// Marker type for no backlight control.
+pub struct NoBacklight;
+
+impl Backlight for NoBacklight {
+    type Error = Infallible;
+
+    fn set_level(&mut self, _level: u8) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// Backlight which is turned on and off with an output pin.
+pub struct BacklightPin<P> {
+    pin: P,
+    active_low: bool,
+}
+
+impl<P: OutputPin> BacklightPin<P> {
+    /// Creates a new backlight which is turned on by setting the pin high.
+    pub fn new(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: false,
+        }
+    }
+
+    /// Creates a new backlight which is turned on by setting the pin low.
+    pub fn new_active_low(pin: P) -> Self {
+        Self {
+            pin,
+            active_low: true,
+        }
+    }
+
+    /// Consumes the backlight and returns the pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+impl<P: OutputPin> Backlight for BacklightPin<P> {
+    type Error = P::Error;
+
+    fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+        if (level > 0) != self.active_low {
+            self.pin.set_high()
+        } else {
+            self.pin.set_low()
+        }
+    }
+}
+
+/// Dimmable backlight which is driven by a PWM channel.
+///
+/// The level is mapped linearly to the duty cycle of the channel.
+pub struct BacklightPwm<P> {
+    pwm: P,
+}
+
+impl<P: SetDutyCycle> BacklightPwm<P> {
+    /// Creates a new backlight from a PWM channel.
+    pub fn new(pwm: P) -> Self {
+        Self { pwm }
+    }
+
+    /// Consumes the backlight and returns the PWM channel.
+    pub fn release(self) -> P {
+        self.pwm
+    }
+}
+
+impl<P: SetDutyCycle> Backlight for BacklightPwm<P> {
+    type Error = P::Error;
+
+    fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+        self.pwm
+            .set_duty_cycle_fraction(u16::from(level), u16::from(u8::MAX))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::{digital, pwm};
+
+    use super::*;
+
+    #[derive(Default)]
+    struct TestPin(Option<bool>);
+
+    impl digital::ErrorType for TestPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for TestPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0 = Some(false);
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0 = Some(true);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn pin_levels() {
+        let mut backlight = BacklightPin::new(TestPin::default());
+        backlight.set_level(1).unwrap();
+        assert_eq!(backlight.pin.0, Some(true));
+        backlight.set_level(0).unwrap();
+        assert_eq!(backlight.pin.0, Some(false));
+
+        let mut backlight = BacklightPin::new_active_low(TestPin::default());
+        backlight.set_level(255).unwrap();
+        assert_eq!(backlight.release().0, Some(false));
+    }
+
+    struct TestPwm(u16);
+
+    impl pwm::ErrorType for TestPwm {
+        type Error = Infallible;
+    }
+
+    impl SetDutyCycle for TestPwm {
+        fn max_duty_cycle(&self) -> u16 {
+            1000
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+            self.0 = duty;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn pwm_duty_cycle() {
+        let mut backlight = BacklightPwm::new(TestPwm(0));
+        backlight.set_level(255).unwrap();
+        assert_eq!(backlight.pwm.0, 1000);
+        backlight.set_level(51).unwrap();
+        assert_eq!(backlight.pwm.0, 200);
+        backlight.set_level(0).unwrap();
+        assert_eq!(backlight.release().0, 0);
+    }
+}
@@ -0,0 +1,161 @@
+//! Rendering in horizontal strips for displays without a full framebuffer.
+
+use core::convert::Infallible;
+
+use embedded_graphics::{draw_target::Translated, prelude::*};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{Interface, PixelWord},
+    models::Model,
+    raw_framebuf::{IntoRawBytes, RawFrameBuf},
+    Display,
+};
+
+impl<DI, M, RST, TE, BL> Display<DI, M, RST, TE, BL>
+where
+    DI: Interface,
+    DI::Word: PixelWord,
+    M: Model,
+    M::ColorFormat: IntoRawBytes,
+    RST: OutputPin,
+{
+    /// Draws a scene in horizontal strips, using a buffer smaller than a full frame.
+    ///
+    /// The buffer is used as a [`RawFrameBuf`] covering as many full display lines as fit into
+    /// it. For every strip, `draw` is called with a draw target which is translated so that
+    /// the scene can be drawn in display coordinates. Everything outside of the current strip is
+    /// clipped. The strip is then sent to the display and the process is repeated until the
+    /// whole display is covered.
+    ///
+    /// Because the scene is drawn once per strip, `draw` must draw the complete scene every
+    /// time it is called, including the background.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer can't hold a single display line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{
+    ///     pixelcolor::Rgb565,
+    ///     prelude::*,
+    ///     primitives::{Circle, PrimitiveStyle},
+    /// };
+    ///
+    /// # tokio_test::block_on(async {
+    /// # let mut display = lcd_async::_mock::new_mock_display().await;
+    /// // 16 lines of 240 Rgb565 pixels
+    /// let mut buffer = [0u8; 240 * 16 * 2];
+    ///
+    /// display
+    ///     .draw_banded(&mut buffer, |target| {
+    ///         target.clear(Rgb565::BLACK)?;
+    ///         Circle::new(Point::new(70, 110), 100)
+    ///             .into_styled(PrimitiveStyle::with_fill(Rgb565::RED))
+    ///             .draw(target)
+    ///     })
+    ///     .await
+    ///     .unwrap();
+    /// # });
+    /// ```
+    pub async fn draw_banded<F>(&mut self, buffer: &mut [u8], mut draw: F) -> Result<(), DI::Error>
+    where
+        F: FnMut(
+            &mut Translated<'_, RawFrameBuf<M::ColorFormat, &mut [u8]>>,
+        ) -> Result<(), Infallible>,
+    {
+        let (width, height) = self.options.display_size();
+        let line_len = usize::from(width) * M::ColorFormat::BYTES_PER_PIXEL;
+        let lines = (buffer.len() / line_len).min(usize::from(height));
+        assert!(lines > 0, "buffer is too small for a single display line");
+
+        let mut y = 0;
+        while y < height {
+            let band_height = (height - y).min(lines as u16);
+            let band_len = usize::from(band_height) * line_len;
+
+            let mut strip = RawFrameBuf::<M::ColorFormat, _>::new(
+                &mut buffer[..band_len],
+                usize::from(width),
+                usize::from(band_height),
+            );
+            // drawing into a RawFrameBuf can't fail
+            let _ = draw(&mut strip.translated(Point::new(0, -i32::from(y))));
+
+            self.show_raw_bytes(0, y, width, band_height, &buffer[..band_len])
+                .await?;
+            y += band_height;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use embedded_graphics::{
+        pixelcolor::{Rgb565, Rgb888},
+        primitives::{PrimitiveStyle, Rectangle},
+    };
+
+    use super::*;
+    use crate::{
+        models::ST7789,
+        testing::{RecordingDelay, VirtualPanel},
+        Builder,
+    };
+
+    #[test]
+    fn draw_banded_covers_display() {
+        tokio_test::block_on(async {
+            let mut display = Builder::new(ST7789, VirtualPanel::for_model::<ST7789>())
+                .display_size(20, 10)
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+
+            // 3 lines per strip, the last strip has a single line
+            let mut buffer = [0u8; 20 * 3 * 2 + 7];
+            let mut calls = 0;
+            display
+                .draw_banded(&mut buffer, |target| {
+                    calls += 1;
+                    target.clear(Rgb565::BLUE)?;
+                    Rectangle::new(Point::new(5, 2), Size::new(3, 6))
+                        .into_styled(PrimitiveStyle::with_fill(Rgb565::WHITE))
+                        .draw(target)
+                })
+                .await
+                .unwrap();
+            assert_eq!(calls, 4);
+
+            let (panel, _, _) = display.release();
+            let white: Vec<(u16, u16)> = (0..10)
+                .flat_map(|y| (0..20).map(move |x| (x, y)))
+                .filter(|&(x, y)| panel.memory_pixel(x, y) == Some(Rgb888::WHITE))
+                .collect();
+            let expected: Vec<(u16, u16)> =
+                (2..8).flat_map(|y| (5..8).map(move |x| (x, y))).collect();
+            assert_eq!(white, expected);
+            assert_eq!(panel.memory_pixel(0, 9), Some(Rgb888::BLUE));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "buffer is too small")]
+    fn draw_banded_buffer_too_small() {
+        tokio_test::block_on(async {
+            let mut display = Builder::new(ST7789, VirtualPanel::for_model::<ST7789>())
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+
+            let mut buffer = [0u8; 100];
+            let _ = display.draw_banded(&mut buffer, |_| Ok(())).await;
+        });
+    }
+}
@@ -1,14 +1,18 @@
 //! [super::Display] builder module
 
 use embedded_hal::digital::{self, OutputPin};
-use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::{delay::DelayNs, digital::Wait};
 
 use crate::{
-    dcs::InterfaceExt,
-    interface::Interface,
-    models::{Model, ModelInitError},
-    options::{ColorInversion, ColorOrder, ModelOptions, Orientation, RefreshOrder},
-    Display,
+    backlight::{Backlight, NoBacklight},
+    dcs::{InterfaceExt, SetTearScanline},
+    interface::{Interface, ReadInterface},
+    models::{self, AnyModel, Model, ModelInitError},
+    options::{
+        ColorInversion, ColorOrder, FrameRate, GammaCurve, ModelOptions, Orientation, RefreshOrder,
+        TearingEffect,
+    },
+    AnyDisplay, Display, NoTePin, TePin,
 };
 
 /// Builder for [Display] instances.
@@ -31,7 +35,7 @@ use crate::{
 ///     .init(&mut delay).await.unwrap();
 /// # });
 /// ```
-pub struct Builder<DI, MODEL, RST>
+pub struct Builder<DI, MODEL, RST, TE = NoTePin, BL = NoBacklight>
 where
     DI: Interface,
     MODEL: Model,
@@ -39,6 +43,10 @@ where
     di: DI,
     model: MODEL,
     rst: Option<RST>,
+    te: TE,
+    te_scanline: u16,
+    backlight: BL,
+    backlight_level: u8,
     options: ModelOptions,
 }
 
@@ -56,16 +64,21 @@ where
             di,
             model,
             rst: None,
+            te: NoTePin,
+            te_scanline: 0,
+            backlight: NoBacklight,
+            backlight_level: u8::MAX,
             options: ModelOptions::full_size::<MODEL>(),
         }
     }
 }
 
-impl<DI, MODEL, RST> Builder<DI, MODEL, RST>
+impl<DI, MODEL, RST, TE, BL> Builder<DI, MODEL, RST, TE, BL>
 where
     DI: Interface,
     MODEL: Model,
     RST: OutputPin,
+    BL: Backlight,
 {
     ///
     /// Sets the invert color flag
@@ -121,6 +134,29 @@ where
         self
     }
 
+    /// Sets the [GammaCurve].
+    ///
+    /// The gamma curve is applied after the init sequence of the model. Defaults to
+    /// [`GammaCurve::Default`], which keeps the gamma curve set by the init sequence.
+    /// [`init`](Self::init) returns [`ConfigurationError::UnsupportedGamma`] before the
+    /// display is initialized if the model doesn't support the gamma curve.
+    #[must_use]
+    pub fn gamma(mut self, gamma: GammaCurve) -> Self {
+        self.options.gamma = gamma;
+        self
+    }
+
+    /// Sets the [FrameRate].
+    ///
+    /// The frame rate is applied after the init sequence of the model. By default the frame
+    /// rate set by the init sequence is used. [`init`](Self::init) returns
+    /// [`ConfigurationError::UnsupportedFrameRate`] if the model doesn't support the settings.
+    #[must_use]
+    pub fn frame_rate(mut self, frame_rate: FrameRate) -> Self {
+        self.options.frame_rate = Some(frame_rate);
+        self
+    }
+
     /// Sets the reset pin.
     ///
     /// ### WARNING
@@ -128,26 +164,104 @@ where
     /// If it wasn't provided the user needs to ensure this is the case.
     ///
     #[must_use]
-    pub fn reset_pin<RST2: OutputPin>(self, rst: RST2) -> Builder<DI, MODEL, RST2> {
+    pub fn reset_pin<RST2: OutputPin>(self, rst: RST2) -> Builder<DI, MODEL, RST2, TE, BL> {
         Builder {
             di: self.di,
             model: self.model,
             rst: Some(rst),
+            te: self.te,
+            te_scanline: self.te_scanline,
+            backlight: self.backlight,
+            backlight_level: self.backlight_level,
+            options: self.options,
+        }
+    }
+
+    /// Sets the tearing effect (TE) input pin.
+    ///
+    /// The tearing effect output of the controller is enabled during [`init`](Self::init)
+    /// and the pin is used by [`Display::show_raw_data_vsync`] to synchronize updates to the
+    /// display refresh.
+    #[must_use]
+    pub fn te_pin<TE2: Wait>(self, te: TE2) -> Builder<DI, MODEL, RST, TE2, BL> {
+        Builder {
+            di: self.di,
+            model: self.model,
+            rst: self.rst,
+            te,
+            te_scanline: self.te_scanline,
+            backlight: self.backlight,
+            backlight_level: self.backlight_level,
+            options: self.options,
+        }
+    }
+
+    /// Sets the backlight.
+    ///
+    /// The backlight is turned off at the start of [`init`](Self::init) and set to the
+    /// [`backlight_level`](Self::backlight_level) once the display was initialized, to hide the
+    /// random contents of the frame memory. See the [`backlight`](crate::backlight) module for
+    /// more information.
+    #[must_use]
+    pub fn backlight<BL2: Backlight>(self, backlight: BL2) -> Builder<DI, MODEL, RST, TE, BL2> {
+        Builder {
+            di: self.di,
+            model: self.model,
+            rst: self.rst,
+            te: self.te,
+            te_scanline: self.te_scanline,
+            backlight,
+            backlight_level: self.backlight_level,
             options: self.options,
         }
     }
 
+    /// Sets the backlight level after initialization.
+    ///
+    /// Defaults to full brightness.
+    #[must_use]
+    pub fn backlight_level(mut self, level: u8) -> Self {
+        self.backlight_level = level;
+        self
+    }
+
+    /// Sets the scanline at which the tearing effect signal is generated.
+    ///
+    /// By default the signal is generated at the start of the vertical blanking period.
+    /// A scanline offset can be used to start updates earlier, if the update is guaranteed
+    /// to stay behind the refresh of the panel. The scanline is only used if a
+    /// [`te_pin`](Self::te_pin) is set.
+    #[must_use]
+    pub fn te_scanline(mut self, scanline: u16) -> Self {
+        self.te_scanline = scanline;
+        self
+    }
+
     ///
     /// Consumes the builder to create a new [Display] with an optional reset [OutputPin].
     /// Blocks using the provided [DelayNs] `delay_source` to perform the display initialization.
-    /// The display will be awake ready to use, no need to call [Display::wake] after init.
+    /// The display will be awake ready to use, no need to call [`wake`](crate::SleepingDisplay::wake) after init.
+    /// The backlight, if set, is turned on after the initialization has completed.
     ///
     /// Returns [InitError] if the area defined by the [`display_size`](Self::display_size)
     /// and [`display_offset`](Self::display_offset) settings is (partially) outside the framebuffer.
     pub async fn init(
         mut self,
         delay_source: &mut impl DelayNs,
-    ) -> Result<Display<DI, MODEL, RST>, InitError<DI::Error, RST::Error>> {
+    ) -> Result<Display<DI, MODEL, RST, TE, BL>, InitError<DI::Error, RST::Error, BL::Error>>
+    where
+        TE: TePin,
+    {
+        self.validate()?;
+
+        self.backlight.set_level(0).map_err(InitError::Backlight)?;
+        self.reset(delay_source).await?;
+
+        self.init_without_reset(delay_source).await
+    }
+
+    /// Checks the options before the hardware is accessed.
+    fn validate(&self) -> Result<(), InitError<DI::Error, RST::Error, BL::Error>> {
         let to_u32 = |(a, b)| (u32::from(a), u32::from(b));
         let (width, height) = to_u32(self.options.display_size);
         let (offset_x, offset_y) = to_u32(self.options.display_offset);
@@ -171,47 +285,206 @@ where
             ));
         }
 
-        match self.rst {
-            Some(ref mut rst) => {
-                rst.set_low().map_err(InitError::ResetPin)?;
-                delay_source.delay_us(MODEL::RESET_DURATION).await;
-                rst.set_high().map_err(InitError::ResetPin)?;
-            }
-            None => self
-                .di
-                .write_command(crate::dcs::SoftReset)
-                .await
-                .map_err(InitError::Interface)?,
+        MODEL::validate_gamma(self.options.gamma).map_err(InitError::InvalidConfiguration)?;
+        if let Some(frame_rate) = self.options.frame_rate {
+            MODEL::validate_frame_rate(frame_rate).map_err(InitError::InvalidConfiguration)?;
         }
 
+        Ok(())
+    }
+
+    /// Initializes a display which was already reset.
+    ///
+    /// The options must have been checked with [`validate`](Self::validate) before.
+    async fn init_without_reset(
+        mut self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<Display<DI, MODEL, RST, TE, BL>, InitError<DI::Error, RST::Error, BL::Error>>
+    where
+        TE: TePin,
+    {
         let madctl = self
             .model
             .init(&mut self.di, delay_source, &self.options)
             .await?;
 
+        if self.options.gamma != GammaCurve::Default {
+            MODEL::set_gamma(&mut self.di, self.options.gamma)
+                .await
+                .map_err(InitError::Interface)?;
+        }
+
+        if let Some(frame_rate) = self.options.frame_rate {
+            MODEL::set_frame_rate(&mut self.di, frame_rate)
+                .await
+                .map_err(InitError::Interface)?;
+        }
+
+        if TE::ENABLED {
+            MODEL::set_tearing_effect(&mut self.di, TearingEffect::Vertical, &self.options)
+                .await
+                .map_err(InitError::Interface)?;
+            if self.te_scanline != 0 {
+                self.di
+                    .write_command(SetTearScanline::new(self.te_scanline))
+                    .await
+                    .map_err(InitError::Interface)?;
+            }
+        }
+
+        self.backlight
+            .set_level(self.backlight_level)
+            .map_err(InitError::Backlight)?;
+
         let display = Display {
             di: self.di,
             model: self.model,
             rst: self.rst,
+            te: self.te,
+            backlight: self.backlight,
+            backlight_level: self.backlight_level,
             options: self.options,
             madctl,
-            sleeping: false, // TODO: init should lock state
         };
 
         Ok(display)
     }
 }
 
+impl<DI, MODEL, RST, TE, BL> Builder<DI, MODEL, RST, TE, BL>
+where
+    DI: ReadInterface,
+    MODEL: Model + Into<AnyModel>,
+    RST: OutputPin,
+    TE: TePin,
+    BL: Backlight,
+{
+    ///
+    /// Consumes the builder to create an [AnyDisplay] for the controller detected from the display ID.
+    ///
+    /// The display is reset and its ID is read using [`AnyModel::detect`]. If the ID isn't
+    /// recognized the model passed to [`Builder::new`] is used as a fallback. If
+    /// [`display_size`](Self::display_size) and [`display_offset`](Self::display_offset) were
+    /// left at their defaults, the display size is set to the full framebuffer of the detected model.
+    ///
+    /// The display is then initialized like [`init`](Self::init) would for the selected model,
+    /// without resetting it a second time. The tearing effect pin and the backlight are passed on
+    /// to the detected display and the backlight is kept off until the initialization is done.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lcd_async::{Builder, AnyDisplay, models::{AnyModel, ST7789}};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # let di = lcd_async::_mock::MockDisplayInterface;
+    /// # let mut delay = lcd_async::_mock::MockDelay;
+    /// let display = Builder::new(ST7789, di)
+    ///     .init_detect(&mut delay)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// // the mock interface returns an unknown ID
+    /// assert_eq!(display.model(), AnyModel::ST7789);
+    /// # });
+    /// ```
+    pub async fn init_detect(
+        mut self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<AnyDisplay<DI, RST, TE, BL>, InitError<DI::Error, RST::Error, BL::Error>> {
+        self.backlight.set_level(0).map_err(InitError::Backlight)?;
+        self.reset(delay_source).await?;
+        // 5ms after a hardware reset, 120ms after a software reset until commands are accepted
+        delay_source.delay_us(120_000).await;
+
+        let model = AnyModel::detect(&mut self.di)
+            .await
+            .map_err(InitError::Interface)?
+            .unwrap_or_else(|| self.model.into());
+
+        let mut options = self.options;
+        if options.display_size == MODEL::FRAMEBUFFER_SIZE && options.display_offset == (0, 0) {
+            options.display_size = model.framebuffer_size();
+        }
+
+        // The display was already reset before the ID was read.
+        macro_rules! init_as {
+            ($model:ident) => {{
+                let builder = Builder {
+                    di: self.di,
+                    model: models::$model,
+                    rst: self.rst,
+                    te: self.te,
+                    te_scanline: self.te_scanline,
+                    backlight: self.backlight,
+                    backlight_level: self.backlight_level,
+                    options,
+                };
+                builder.validate()?;
+                builder
+                    .init_without_reset(delay_source)
+                    .await
+                    .map(AnyDisplay::$model)
+            }};
+        }
+
+        match model {
+            AnyModel::GC9107 => init_as!(GC9107),
+            AnyModel::GC9A01 => init_as!(GC9A01),
+            AnyModel::ILI9341Rgb565 => init_as!(ILI9341Rgb565),
+            AnyModel::ILI9342CRgb565 => init_as!(ILI9342CRgb565),
+            AnyModel::ILI9486Rgb565 => init_as!(ILI9486Rgb565),
+            AnyModel::ILI9488Rgb565 => init_as!(ILI9488Rgb565),
+            AnyModel::ST7735s => init_as!(ST7735s),
+            AnyModel::ST7789 => init_as!(ST7789),
+            AnyModel::ST7796 => init_as!(ST7796),
+        }
+    }
+}
+
+impl<DI, MODEL, RST, TE, BL> Builder<DI, MODEL, RST, TE, BL>
+where
+    DI: Interface,
+    MODEL: Model,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    // Resets the display using the reset pin if present or a software reset otherwise.
+    async fn reset(
+        &mut self,
+        delay_source: &mut impl DelayNs,
+    ) -> Result<(), InitError<DI::Error, RST::Error, BL::Error>> {
+        match self.rst {
+            Some(ref mut rst) => {
+                rst.set_low().map_err(InitError::ResetPin)?;
+                delay_source.delay_us(MODEL::RESET_DURATION).await;
+                rst.set_high().map_err(InitError::ResetPin)?;
+            }
+            None => self
+                .di
+                .write_command(crate::dcs::SoftReset)
+                .await
+                .map_err(InitError::Interface)?,
+        }
+
+        Ok(())
+    }
+}
+
 /// Error returned by [`Builder::init`].
+#[non_exhaustive]
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub enum InitError<DI, P> {
+pub enum InitError<DI, P, BL = core::convert::Infallible> {
     /// Error caused by the display interface.
     Interface(DI),
 
     /// Error caused by the reset pin's [`OutputPin`](embedded_hal::digital::OutputPin) implementation.
     ResetPin(P),
 
+    /// Error caused by the [`Backlight`] implementation.
+    Backlight(BL),
+
     /// Invalid configuration error.
     ///
     /// This error is returned when the configuration passed to the builder is
@@ -244,9 +517,26 @@ pub enum ConfigurationError {
     /// the controller's framebuffer. To resolve this, reduce the offset to a maximum value of
     /// [`FRAMEBUFFER_SIZE`](Model::FRAMEBUFFER_SIZE) minus [`display_size`](Builder::display_size).
     InvalidDisplayOffset,
+    /// Invalid partial area.
+    ///
+    /// The rows passed to [`Display::enter_partial_mode`](crate::Display::enter_partial_mode)
+    /// are empty or extend beyond the [`display_size`](Builder::display_size).
+    InvalidPartialArea,
+    /// Unsupported frame rate.
+    ///
+    /// The selected model doesn't support setting the frame rate or the
+    /// [`FrameRate`](crate::options::FrameRate) settings are outside the valid range of the
+    /// controller.
+    UnsupportedFrameRate,
+    /// Unsupported gamma curve.
+    ///
+    /// The selected model doesn't support the [`GammaCurve`](crate::options::GammaCurve), e.g.
+    /// custom gamma tables on a model without gamma correction registers or tables with the
+    /// wrong length, see [`Model::GAMMA_TABLE_LEN`].
+    UnsupportedGamma,
 }
 
-impl<DiError, P> From<ModelInitError<DiError>> for InitError<DiError, P> {
+impl<DiError, P, BL> From<ModelInitError<DiError>> for InitError<DiError, P, BL> {
     fn from(value: ModelInitError<DiError>) -> Self {
         match value {
             ModelInitError::Interface(e) => Self::Interface(e),
@@ -1,6 +1,6 @@
 //! MIPI DCS commands.
 
-use crate::interface::Interface;
+use crate::interface::{DummyCycles, Interface, ReadInterface};
 
 #[macro_use]
 pub(crate) mod macros;
@@ -17,10 +17,34 @@ mod set_scroll_area;
 pub use set_scroll_area::*;
 mod set_scroll_start;
 pub use set_scroll_start::*;
+mod set_partial_rows;
+pub use set_partial_rows::*;
+mod set_partial_columns;
+pub use set_partial_columns::*;
 mod set_tearing_effect;
 pub use set_tearing_effect::*;
+mod set_tear_scanline;
+pub use set_tear_scanline::*;
+mod set_gamma_curve;
+pub use set_gamma_curve::*;
 mod set_invert_mode;
 pub use set_invert_mode::*;
+mod write_display_brightness;
+pub use write_display_brightness::*;
+mod write_ctrl_display;
+pub use write_ctrl_display::*;
+mod write_cabc_mode;
+pub use write_cabc_mode::*;
+mod write_cabc_minimum_brightness;
+pub use write_cabc_minimum_brightness::*;
+mod read_display_id;
+pub use read_display_id::*;
+mod read_display_status;
+pub use read_display_status::*;
+mod read_power_mode;
+pub use read_power_mode::*;
+mod get_scanline;
+pub use get_scanline::*;
 
 /// Common trait for DCS commands.
 ///
@@ -75,6 +99,79 @@ pub trait InterfaceExt: Interface {
 
 impl<T: Interface> InterfaceExt for T {}
 
+/// Common trait for DCS read commands.
+///
+/// The methods in this trait are used to decode the parameters returned by the display.
+pub trait DcsReadCommand {
+    /// The decoded response.
+    type Response;
+
+    /// Returns the instruction code.
+    fn instruction(&self) -> u8;
+
+    /// Returns the number of parameter bytes returned by the display.
+    ///
+    /// [`ReadInterfaceExt::read_command_response`] reads the response into a 16 byte buffer, so
+    /// the response must not be longer than 16 bytes.
+    fn response_len(&self) -> usize;
+
+    /// Returns the dummy cycles before the response in serial interfaces.
+    ///
+    /// The default implementation returns [`DummyCycles::None`].
+    fn dummy_cycles(&self) -> DummyCycles {
+        DummyCycles::None
+    }
+
+    /// Decodes the response from the parameter bytes returned by the display.
+    fn parse_response(&self, buffer: &[u8]) -> Self::Response;
+}
+
+/// An extension trait for [`ReadInterface`] with support for reading DCS commands.
+pub trait ReadInterfaceExt: ReadInterface {
+    /// Sends a DCS read command and decodes the response.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`response_len`](DcsReadCommand::response_len) is larger than 16 bytes.
+    fn read_command_response<C: DcsReadCommand>(
+        &mut self,
+        command: C,
+    ) -> impl core::future::Future<Output = Result<C::Response, Self::Error>> {
+        async move {
+            let mut param_bytes: [u8; 16] = [0; 16];
+            let n = command.response_len();
+            assert!(
+                n <= param_bytes.len(),
+                "DCS read responses are limited to 16 bytes"
+            );
+            self.read_raw(
+                command.instruction(),
+                command.dummy_cycles(),
+                &mut param_bytes[..n],
+            )
+            .await?;
+            Ok(command.parse_response(&param_bytes[..n]))
+        }
+    }
+
+    /// Sends a raw read command with the given `instruction` and reads the response into `buffer`.
+    ///
+    /// The `dummy` cycles before the response are skipped, see [`DummyCycles`].
+    ///
+    /// This method is intended to be used for reading registers which are not part of the MIPI DCS
+    /// user command set, e.g. manufacturer specific ID registers.
+    fn read_raw(
+        &mut self,
+        instruction: u8,
+        dummy: DummyCycles,
+        buffer: &mut [u8],
+    ) -> impl core::future::Future<Output = Result<(), Self::Error>> {
+        async move { self.read_command(instruction, dummy, buffer).await }
+    }
+}
+
+impl<T: ReadInterface> ReadInterfaceExt for T {}
+
 // DCS commands that don't use any parameters
 
 dcs_basic_command!(
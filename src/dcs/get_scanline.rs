@@ -0,0 +1,43 @@
+//! Module for the GSCAN scanline read instruction
+
+use crate::interface::DummyCycles;
+
+use super::DcsReadCommand;
+
+/// Get Scanline
+///
+/// Returns the scanline which is currently refreshed by the display controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GetScanline;
+
+impl DcsReadCommand for GetScanline {
+    type Response = u16;
+
+    fn instruction(&self) -> u8 {
+        0x45
+    }
+
+    fn response_len(&self) -> usize {
+        2
+    }
+
+    fn dummy_cycles(&self) -> DummyCycles {
+        DummyCycles::Clock
+    }
+
+    fn parse_response(&self, buffer: &[u8]) -> Self::Response {
+        u16::from_be_bytes([buffer[0], buffer[1]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gscan_parses_response_properly() {
+        assert_eq!(GetScanline.instruction(), 0x45);
+        assert_eq!(GetScanline.parse_response(&[0x01, 0x2C]), 300);
+    }
+}
@@ -0,0 +1,65 @@
+//! Module for the RDDID display identification read instruction
+
+use crate::interface::DummyCycles;
+
+use super::DcsReadCommand;
+
+/// Read Display ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadDisplayId;
+
+/// Display identification returned by [`ReadDisplayId`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DisplayId {
+    /// Module manufacturer ID (ID1).
+    pub manufacturer: u8,
+    /// Module/driver version ID (ID2).
+    pub version: u8,
+    /// Module/driver ID (ID3).
+    pub driver: u8,
+}
+
+impl DcsReadCommand for ReadDisplayId {
+    type Response = DisplayId;
+
+    fn instruction(&self) -> u8 {
+        0x04
+    }
+
+    fn response_len(&self) -> usize {
+        3
+    }
+
+    fn dummy_cycles(&self) -> DummyCycles {
+        DummyCycles::Clock
+    }
+
+    fn parse_response(&self, buffer: &[u8]) -> Self::Response {
+        DisplayId {
+            manufacturer: buffer[0],
+            version: buffer[1],
+            driver: buffer[2],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rddid_parses_response_properly() {
+        assert_eq!(ReadDisplayId.instruction(), 0x04);
+        assert_eq!(ReadDisplayId.response_len(), 3);
+        assert_eq!(
+            ReadDisplayId.parse_response(&[0x85, 0x85, 0x52]),
+            DisplayId {
+                manufacturer: 0x85,
+                version: 0x85,
+                driver: 0x52,
+            }
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Module for the RDDST display status read instruction
+
+use crate::{
+    interface::DummyCycles,
+    options::{ColorOrder, TearingEffect},
+};
+
+use super::DcsReadCommand;
+
+/// Read Display Status
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadDisplayStatus;
+
+/// Display status returned by [`ReadDisplayStatus`].
+///
+/// The raw 32 bit status word is available through [`DisplayStatus::bits`],
+/// the most commonly used flags are decoded by the accessor methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DisplayStatus(u32);
+
+impl DisplayStatus {
+    /// Creates a display status from the raw status word.
+    pub const fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw status word.
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    const fn bit(&self, bit: u32) -> bool {
+        self.0 & (1 << bit) != 0
+    }
+
+    /// Returns `true` if the booster voltage is on.
+    pub const fn booster_on(&self) -> bool {
+        self.bit(31)
+    }
+
+    /// Returns the subpixel order.
+    pub const fn color_order(&self) -> ColorOrder {
+        if self.bit(26) {
+            ColorOrder::Bgr
+        } else {
+            ColorOrder::Rgb
+        }
+    }
+
+    /// Returns `true` if idle mode is on.
+    pub const fn idle_mode_on(&self) -> bool {
+        self.bit(19)
+    }
+
+    /// Returns `true` if partial mode is on.
+    pub const fn partial_mode_on(&self) -> bool {
+        self.bit(18)
+    }
+
+    /// Returns `true` if the display is out of sleep mode.
+    pub const fn sleep_out(&self) -> bool {
+        self.bit(17)
+    }
+
+    /// Returns `true` if the display is in normal mode.
+    pub const fn normal_mode_on(&self) -> bool {
+        self.bit(16)
+    }
+
+    /// Returns `true` if color inversion is on.
+    pub const fn inversion_on(&self) -> bool {
+        self.bit(13)
+    }
+
+    /// Returns `true` if the display is on.
+    pub const fn display_on(&self) -> bool {
+        self.bit(10)
+    }
+
+    /// Returns the tearing effect output setting.
+    pub const fn tearing_effect(&self) -> TearingEffect {
+        match (self.bit(9), self.bit(5)) {
+            (false, _) => TearingEffect::Off,
+            (true, false) => TearingEffect::Vertical,
+            (true, true) => TearingEffect::HorizontalAndVertical,
+        }
+    }
+}
+
+impl DcsReadCommand for ReadDisplayStatus {
+    type Response = DisplayStatus;
+
+    fn instruction(&self) -> u8 {
+        0x09
+    }
+
+    fn response_len(&self) -> usize {
+        4
+    }
+
+    fn dummy_cycles(&self) -> DummyCycles {
+        DummyCycles::Clock
+    }
+
+    fn parse_response(&self, buffer: &[u8]) -> Self::Response {
+        DisplayStatus(u32::from_be_bytes([
+            buffer[0], buffer[1], buffer[2], buffer[3],
+        ]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rddst_parses_response_properly() {
+        let status = ReadDisplayStatus.parse_response(&[0x80, 0x53, 0x06, 0x20]);
+
+        assert_eq!(ReadDisplayStatus.instruction(), 0x09);
+        assert_eq!(status.bits(), 0x8053_0620);
+        assert!(status.booster_on());
+        assert_eq!(status.color_order(), ColorOrder::Rgb);
+        assert!(!status.idle_mode_on());
+        assert!(status.sleep_out());
+        assert!(status.normal_mode_on());
+        assert!(status.display_on());
+        assert!(!status.inversion_on());
+        assert_eq!(
+            status.tearing_effect(),
+            TearingEffect::HorizontalAndVertical
+        );
+    }
+}
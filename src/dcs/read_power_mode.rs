@@ -0,0 +1,89 @@
+//! Module for the RDDPM power mode read instruction
+
+use super::DcsReadCommand;
+
+/// Read Power Mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReadPowerMode;
+
+/// Power mode returned by [`ReadPowerMode`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PowerMode(u8);
+
+impl PowerMode {
+    /// Creates a power mode from the raw register value.
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    /// Returns the raw register value.
+    pub const fn bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns `true` if the booster voltage is on.
+    pub const fn booster_on(&self) -> bool {
+        self.0 & 0b1000_0000 != 0
+    }
+
+    /// Returns `true` if idle mode is on.
+    pub const fn idle_mode_on(&self) -> bool {
+        self.0 & 0b0100_0000 != 0
+    }
+
+    /// Returns `true` if partial mode is on.
+    pub const fn partial_mode_on(&self) -> bool {
+        self.0 & 0b0010_0000 != 0
+    }
+
+    /// Returns `true` if the display is out of sleep mode.
+    pub const fn sleep_out(&self) -> bool {
+        self.0 & 0b0001_0000 != 0
+    }
+
+    /// Returns `true` if the display is in normal mode.
+    pub const fn normal_mode_on(&self) -> bool {
+        self.0 & 0b0000_1000 != 0
+    }
+
+    /// Returns `true` if the display is on.
+    pub const fn display_on(&self) -> bool {
+        self.0 & 0b0000_0100 != 0
+    }
+}
+
+impl DcsReadCommand for ReadPowerMode {
+    type Response = PowerMode;
+
+    fn instruction(&self) -> u8 {
+        0x0A
+    }
+
+    fn response_len(&self) -> usize {
+        1
+    }
+
+    fn parse_response(&self, buffer: &[u8]) -> Self::Response {
+        PowerMode(buffer[0])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rddpm_parses_response_properly() {
+        let power_mode = ReadPowerMode.parse_response(&[0b1001_1100]);
+
+        assert_eq!(ReadPowerMode.instruction(), 0x0A);
+        assert!(power_mode.booster_on());
+        assert!(!power_mode.idle_mode_on());
+        assert!(!power_mode.partial_mode_on());
+        assert!(power_mode.sleep_out());
+        assert!(power_mode.normal_mode_on());
+        assert!(power_mode.display_on());
+    }
+}
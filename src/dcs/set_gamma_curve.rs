@@ -0,0 +1,50 @@
+//! Module for the GAMSET gamma curve instruction constructors
+
+use crate::options::PredefinedGamma;
+
+use super::DcsCommand;
+
+/// Gamma Set
+///
+/// Selects one of the gamma curves which are predefined by the controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SetGammaCurve(PredefinedGamma);
+
+impl SetGammaCurve {
+    /// Creates a new Gamma Set command.
+    pub const fn new(curve: PredefinedGamma) -> Self {
+        Self(curve)
+    }
+}
+
+impl DcsCommand for SetGammaCurve {
+    fn instruction(&self) -> u8 {
+        0x26
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = match self.0 {
+            PredefinedGamma::Curve1 => 0x01,
+            PredefinedGamma::Curve2 => 0x02,
+            PredefinedGamma::Curve3 => 0x04,
+            PredefinedGamma::Curve4 => 0x08,
+        };
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gamset_fills_curve_properly() {
+        let gamset = SetGammaCurve::new(PredefinedGamma::Curve3);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(gamset.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x04]);
+    }
+}
@@ -0,0 +1,55 @@
+//! Module for the PLTAR partial columns instruction constructors
+
+use super::DcsCommand;
+
+/// Set Partial Columns
+///
+/// Defines the range of frame memory columns which are shown in partial mode, on controllers
+/// which support a vertical partial area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SetPartialColumns {
+    start: u16,
+    end: u16,
+}
+
+impl SetPartialColumns {
+    /// Creates a new Set Partial Columns command.
+    ///
+    /// Both the `start` and the `end` column are inclusive.
+    pub const fn new(start: u16, end: u16) -> Self {
+        Self { start, end }
+    }
+}
+
+impl DcsCommand for SetPartialColumns {
+    fn instruction(&self) -> u8 {
+        0x31
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        let start_bytes = self.start.to_be_bytes();
+        let end_bytes = self.end.to_be_bytes();
+
+        buffer[0] = start_bytes[0];
+        buffer[1] = start_bytes[1];
+        buffer[2] = end_bytes[0];
+        buffer[3] = end_bytes[1];
+
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pltar_fills_buffer_properly() {
+        let command = SetPartialColumns::new(0, 239);
+
+        let mut buffer = [0u8; 4];
+        assert_eq!(command.fill_params_buf(&mut buffer), 4);
+        assert_eq!(buffer, [0, 0, 0, 0xEF]);
+    }
+}
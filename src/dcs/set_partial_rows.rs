@@ -0,0 +1,55 @@
+//! Module for the PTLAR partial area instruction constructors
+
+use super::DcsCommand;
+
+/// Set Partial Rows
+///
+/// Defines the range of frame memory rows which are shown in partial mode. Rows outside of
+/// this range are driven with the non-display color of the controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SetPartialRows {
+    start: u16,
+    end: u16,
+}
+
+impl SetPartialRows {
+    /// Creates a new Set Partial Rows command.
+    ///
+    /// Both the `start` and the `end` row are inclusive.
+    pub const fn new(start: u16, end: u16) -> Self {
+        Self { start, end }
+    }
+}
+
+impl DcsCommand for SetPartialRows {
+    fn instruction(&self) -> u8 {
+        0x30
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        let start_bytes = self.start.to_be_bytes();
+        let end_bytes = self.end.to_be_bytes();
+
+        buffer[0] = start_bytes[0];
+        buffer[1] = start_bytes[1];
+        buffer[2] = end_bytes[0];
+        buffer[3] = end_bytes[1];
+
+        4
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ptlar_fills_buffer_properly() {
+        let command = SetPartialRows::new(16, 319);
+
+        let mut buffer = [0u8; 4];
+        assert_eq!(command.fill_params_buf(&mut buffer), 4);
+        assert_eq!(buffer, [0, 0x10, 0x1, 0x3F]);
+    }
+}
@@ -0,0 +1,44 @@
+//! Module for the WRCABCMB CABC minimum brightness instruction constructors
+
+use super::DcsCommand;
+
+/// Write CABC Minimum Brightness
+///
+/// Sets the lowest brightness value the content adaptive brightness control is allowed to
+/// use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WriteCabcMinimumBrightness(u8);
+
+impl WriteCabcMinimumBrightness {
+    /// Creates a new Write CABC Minimum Brightness command.
+    pub const fn new(brightness: u8) -> Self {
+        Self(brightness)
+    }
+}
+
+impl DcsCommand for WriteCabcMinimumBrightness {
+    fn instruction(&self) -> u8 {
+        0x5E
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = self.0;
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrcabcmb_fills_brightness_properly() {
+        let wrcabcmb = WriteCabcMinimumBrightness::new(0x40);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(wrcabcmb.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x40]);
+    }
+}
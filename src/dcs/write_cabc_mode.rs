@@ -0,0 +1,51 @@
+//! Module for the WRCACE content adaptive brightness control instruction constructors
+
+use crate::options::CabcMode;
+
+use super::DcsCommand;
+
+/// Write Content Adaptive Brightness Control
+///
+/// Selects the content adaptive brightness control (CABC) mode, which reduces the backlight
+/// brightness depending on the displayed image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WriteCabcMode(CabcMode);
+
+impl WriteCabcMode {
+    /// Creates a new Write Content Adaptive Brightness Control command.
+    pub const fn new(mode: CabcMode) -> Self {
+        Self(mode)
+    }
+}
+
+impl DcsCommand for WriteCabcMode {
+    fn instruction(&self) -> u8 {
+        0x55
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = match self.0 {
+            CabcMode::Off => 0x00,
+            CabcMode::UserInterface => 0x01,
+            CabcMode::StillPicture => 0x02,
+            CabcMode::MovingImage => 0x03,
+        };
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrcace_fills_mode_properly() {
+        let wrcace = WriteCabcMode::new(CabcMode::MovingImage);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(wrcace.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x03]);
+    }
+}
@@ -0,0 +1,62 @@
+//! Module for the WRCTRLD display control instruction constructors
+
+use super::DcsCommand;
+
+/// Write CTRL Display
+///
+/// Controls the brightness control block, display dimming and the backlight output of the
+/// display controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WriteCtrlDisplay {
+    brightness_control: bool,
+    dimming: bool,
+    backlight: bool,
+}
+
+impl WriteCtrlDisplay {
+    /// Creates a new Write CTRL Display command.
+    ///
+    /// `brightness_control` enables the brightness registers (BCTRL), `dimming` enables the
+    /// smooth transition between brightness values (DD) and `backlight` turns on the backlight
+    /// control output (BL).
+    pub const fn new(brightness_control: bool, dimming: bool, backlight: bool) -> Self {
+        Self {
+            brightness_control,
+            dimming,
+            backlight,
+        }
+    }
+}
+
+impl DcsCommand for WriteCtrlDisplay {
+    fn instruction(&self) -> u8 {
+        0x53
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = u8::from(self.brightness_control) << 5
+            | u8::from(self.dimming) << 3
+            | u8::from(self.backlight) << 2;
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrctrld_fills_buffer_properly() {
+        let mut buffer = [0u8; 1];
+
+        let wrctrld = WriteCtrlDisplay::new(true, false, false);
+        assert_eq!(wrctrld.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x20]);
+
+        let wrctrld = WriteCtrlDisplay::new(true, true, true);
+        assert_eq!(wrctrld.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0x2C]);
+    }
+}
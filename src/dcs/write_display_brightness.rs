@@ -0,0 +1,45 @@
+//! Module for the WRDISBV display brightness instruction constructors
+
+use super::DcsCommand;
+
+/// Write Display Brightness
+///
+/// Sets the brightness value of the display, from `0` (darkest) to `255` (brightest). On LCD
+/// controllers the brightness is output as a PWM signal for the backlight driver and requires
+/// brightness control to be enabled with [`WriteCtrlDisplay`](super::WriteCtrlDisplay).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WriteDisplayBrightness(u8);
+
+impl WriteDisplayBrightness {
+    /// Creates a new Write Display Brightness command.
+    pub const fn new(brightness: u8) -> Self {
+        Self(brightness)
+    }
+}
+
+impl DcsCommand for WriteDisplayBrightness {
+    fn instruction(&self) -> u8 {
+        0x51
+    }
+
+    fn fill_params_buf(&self, buffer: &mut [u8]) -> usize {
+        buffer[0] = self.0;
+
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrdisbv_fills_brightness_properly() {
+        let wrdisbv = WriteDisplayBrightness::new(0xAF);
+
+        let mut buffer = [0u8; 1];
+        assert_eq!(wrdisbv.fill_params_buf(&mut buffer), 1);
+        assert_eq!(buffer, [0xAF]);
+    }
+}
@@ -3,16 +3,30 @@
 mod spi;
 pub use spi::*;
 
+mod spi_bus;
+pub use spi_bus::*;
+
+mod spi9bit;
+pub use spi9bit::*;
+
 mod parallel;
 pub use parallel::*;
 
+mod qspi;
+pub use qspi::*;
+
+/// Number of words in the stack buffers used to send repeated data.
+///
+/// This is 512 bytes for 8 bit interfaces, which keeps the number of transfers for a full screen
+/// fill low without requiring a large stack.
+pub(crate) const REPEAT_BUFFER_LEN: usize = 512;
+
 /// Command and pixel interface
 pub trait Interface {
     /// The native width of the interface
     ///
     /// In most cases this will be u8, except for larger parallel interfaces such as
-    /// 16 bit (currently supported)
-    /// or 9 or 18 bit (currently unsupported)
+    /// 9 or 16 bit (u16) or 18 bit (u32)
     type Word: Copy;
 
     /// Error type
@@ -36,6 +50,75 @@ pub trait Interface {
         &mut self,
         data: &[Self::Word],
     ) -> impl core::future::Future<Output = Result<(), Self::Error>>;
+
+    /// Send the same word `count` times, e.g. to fill an area with a single color.
+    /// `WriteMemoryStart` (or equivalent) must be sent before calling this function.
+    ///
+    /// The default implementation fills a buffer of 512 words on the stack and sends it
+    /// repeatedly with [`send_data_slice`](Self::send_data_slice). Interfaces which can repeat a word
+    /// more efficiently, e.g. by using DMA without incrementing the source address, should
+    /// override this method.
+    fn send_repeated(
+        &mut self,
+        word: Self::Word,
+        count: u32,
+    ) -> impl core::future::Future<Output = Result<(), Self::Error>> {
+        async move {
+            let buffer = [word; REPEAT_BUFFER_LEN];
+            let mut remaining = count as usize;
+            while remaining > 0 {
+                let len = remaining.min(buffer.len());
+                self.send_data_slice(&buffer[..len]).await?;
+                remaining -= len;
+            }
+            Ok(())
+        }
+    }
+
+    /// Completes a memory write after all data was sent.
+    ///
+    /// [`Display`](crate::Display) calls this at the end of every memory write, e.g. to wait for
+    /// pending transfers and release the chip select. The default implementation does nothing.
+    fn flush(&mut self) -> impl core::future::Future<Output = Result<(), Self::Error>> {
+        async { Ok(()) }
+    }
+}
+
+/// Command interface with support for reading data back from the display controller.
+///
+/// This is an optional extension of [`Interface`]. Not every physical connection
+/// can read from the controller, e.g. SPI modules without a MISO line or parallel
+/// interfaces without a read strobe pin.
+pub trait ReadInterface: Interface {
+    /// Send a command and read its response parameters into `buffer`.
+    ///
+    /// Exactly `buffer.len()` parameter bytes are read. The `dummy` cycles the controller
+    /// inserts before the first valid parameter are skipped by the implementation and
+    /// are not part of `buffer`.
+    fn read_command(
+        &mut self,
+        command: u8,
+        dummy: DummyCycles,
+        buffer: &mut [u8],
+    ) -> impl core::future::Future<Output = Result<(), Self::Error>>;
+}
+
+/// Dummy cycles inserted by the controller before the response of a read command.
+///
+/// The dummy cycles depend on the command and are only relevant for serial interfaces.
+/// Parallel interfaces always discard the first read cycle after a command, which is a dummy
+/// cycle for every read command, and ignore this setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum DummyCycles {
+    /// No dummy cycles, e.g. for 8 bit reads like RDDPM (0x0A).
+    None,
+    /// A single dummy clock cycle, e.g. for the 24 and 32 bit reads RDDID (0x04) and
+    /// RDDST (0x09).
+    Clock,
+    /// A dummy byte, e.g. for the manufacturer specific Read ID4 (0xD3) command.
+    Byte,
 }
 
 impl<T: Interface + ?Sized> Interface for &mut T {
@@ -50,6 +133,102 @@ impl<T: Interface + ?Sized> Interface for &mut T {
     async fn send_data_slice(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
         T::send_data_slice(self, data).await
     }
+
+    async fn send_repeated(&mut self, word: Self::Word, count: u32) -> Result<(), Self::Error> {
+        T::send_repeated(self, word, count).await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        T::flush(self).await
+    }
+}
+
+impl<T: ReadInterface + ?Sized> ReadInterface for &mut T {
+    async fn read_command(
+        &mut self,
+        command: u8,
+        dummy: DummyCycles,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        T::read_command(self, command, dummy, buffer).await
+    }
+}
+
+/// An interface word which raw pixel bytes can be packed into.
+///
+/// This is used by [`Display::show_raw_bytes`](crate::Display::show_raw_bytes) to send pixel
+/// data in the [`IntoRawBytes`](crate::raw_framebuf::IntoRawBytes) format, e.g. from a
+/// [`RawFrameBuf`](crate::raw_framebuf::RawFrameBuf), over interfaces with words wider than
+/// a byte.
+pub trait PixelWord: Copy + Default + PartialEq {
+    /// Packs the raw bytes of a single pixel into interface words.
+    ///
+    /// Returns the number of words written to `words`. Pixel formats which have no special
+    /// packing for the interface kind are sent as one byte per word.
+    fn pack_pixel(kind: InterfaceKind, pixel: &[u8], words: &mut [Self; 3]) -> usize;
+
+    /// Returns the raw bytes as interface words, if they don't need to be packed.
+    ///
+    /// This allows byte wide interfaces to send pixel data without copying it. The default
+    /// implementation returns `None`.
+    fn as_words(bytes: &[u8]) -> Option<&[Self]> {
+        let _ = bytes;
+        None
+    }
+}
+
+impl PixelWord for u8 {
+    fn pack_pixel(_kind: InterfaceKind, pixel: &[u8], words: &mut [Self; 3]) -> usize {
+        words[..pixel.len()].copy_from_slice(pixel);
+        pixel.len()
+    }
+
+    fn as_words(bytes: &[u8]) -> Option<&[Self]> {
+        Some(bytes)
+    }
+}
+
+impl PixelWord for u16 {
+    fn pack_pixel(kind: InterfaceKind, pixel: &[u8], words: &mut [Self; 3]) -> usize {
+        match (kind, pixel) {
+            // 18 bit color is sent in two transfers of 9 bits:
+            // R5-R0 G5-G3, followed by G2-G0 B5-B0.
+            (InterfaceKind::Parallel9Bit, &[r, g, b]) => {
+                let (r, g, b) = (u16::from(r >> 2), u16::from(g >> 2), u16::from(b >> 2));
+                words[0] = r << 3 | g >> 3;
+                words[1] = (g & 0x07) << 6 | b;
+                2
+            }
+            // 16 bit color is sent in a single transfer, with the first byte in the upper half.
+            (InterfaceKind::Parallel16Bit, &[high, low]) => {
+                words[0] = u16::from_be_bytes([high, low]);
+                1
+            }
+            _ => pack_bytes(pixel, words),
+        }
+    }
+}
+
+impl PixelWord for u32 {
+    fn pack_pixel(kind: InterfaceKind, pixel: &[u8], words: &mut [Self; 3]) -> usize {
+        match (kind, pixel) {
+            // 18 bit color is sent in a single transfer: R5-R0 G5-G0 B5-B0.
+            (InterfaceKind::Parallel18Bit, &[r, g, b]) => {
+                let (r, g, b) = (u32::from(r >> 2), u32::from(g >> 2), u32::from(b >> 2));
+                words[0] = r << 12 | g << 6 | b;
+                1
+            }
+            _ => pack_bytes(pixel, words),
+        }
+    }
+}
+
+/// Sends every byte in a separate word.
+fn pack_bytes<W: From<u8>>(pixel: &[u8], words: &mut [W; 3]) -> usize {
+    for (word, &byte) in words.iter_mut().zip(pixel) {
+        *word = W::from(byte);
+    }
+    pixel.len()
 }
 
 /// Interface kind.
@@ -66,15 +245,90 @@ pub enum InterfaceKind {
     /// distinguish between data and command words.
     Serial4Line,
 
+    /// Serial interface without data/command pin.
+    ///
+    /// 3-wire SPI style interface which transmits a data/command bit before
+    /// every byte, resulting in 9 bits per word.
+    Serial3Line,
+
     /// 8 bit parallel interface.
     ///
     /// 8080 style parallel interface with 8 data pins and chip select, write enable,
     /// and command/data signals.
     Parallel8Bit,
 
+    /// 9 bit parallel interface.
+    ///
+    /// 8080 style parallel interface with 9 data pins and chip select, write enable,
+    /// and command/data signals. Commands and parameters only use the lower 8 data pins.
+    Parallel9Bit,
+
     /// 16 bit parallel interface.
     ///
     /// 8080 style parallel interface with 16 data pins and chip select, write enable,
     /// and command/data signals.
     Parallel16Bit,
+
+    /// 18 bit parallel interface.
+    ///
+    /// 8080 style parallel interface with 18 data pins and chip select, write enable,
+    /// and command/data signals. Commands and parameters only use the lower 8 data pins.
+    Parallel18Bit,
+
+    /// Quad-SPI interface.
+    ///
+    /// Commands are sent on a single data line, pixel data is sent on four
+    /// data lines.
+    Qspi,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_rgb666_9bit() {
+        let mut words = [0u16; 3];
+        let count = u16::pack_pixel(
+            InterfaceKind::Parallel9Bit,
+            &[0b1111_1100, 0b1010_1000, 0b0000_0100],
+            &mut words,
+        );
+
+        assert_eq!(
+            &words[..count],
+            &[0b111111 << 3 | 0b101, 0b010 << 6 | 0b000001]
+        );
+    }
+
+    #[test]
+    fn pack_rgb565_16bit() {
+        let mut words = [0u16; 3];
+        let count = u16::pack_pixel(InterfaceKind::Parallel16Bit, &[0xF8, 0x1F], &mut words);
+
+        assert_eq!(&words[..count], &[0xF81F]);
+    }
+
+    #[test]
+    fn pack_rgb666_18bit() {
+        let mut words = [0u32; 3];
+        let count = u32::pack_pixel(
+            InterfaceKind::Parallel18Bit,
+            &[0b1111_1100, 0b1010_1000, 0b0000_0100],
+            &mut words,
+        );
+
+        assert_eq!(
+            &words[..count],
+            &[0b111111 << 12 | 0b101010 << 6 | 0b000001]
+        );
+    }
+
+    #[test]
+    fn pack_bytes_fallback() {
+        let mut words = [0u32; 3];
+        let count = u32::pack_pixel(InterfaceKind::Parallel18Bit, &[0x12, 0x34], &mut words);
+
+        assert_eq!(&words[..count], &[0x12, 0x34]);
+    }
 }
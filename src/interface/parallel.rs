@@ -1,10 +1,11 @@
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::{ErrorType, OutputPin};
 
-use super::{Interface, InterfaceKind};
+use super::{DummyCycles, Interface, InterfaceKind, ReadInterface};
 
 /// This trait represents the data pins of a parallel bus.
 ///
-/// See [Generic8BitBus] and [Generic16BitBus] for generic implementations.
+/// See [Generic8BitBus], [Generic9BitBus], [Generic16BitBus] and [Generic18BitBus] for
+/// generic implementations.
 pub trait OutputBus {
     /// [u8] for 8-bit buses, [u16] for 16-bit buses, etc.
     type Word: Copy;
@@ -19,6 +20,43 @@ pub trait OutputBus {
     fn set_value(&mut self, value: Self::Word) -> Result<(), Self::Error>;
 }
 
+/// This trait represents a parallel bus peripheral which writes whole slices asynchronously.
+///
+/// Unlike [`OutputBus`], an implementation of this trait is responsible for generating the
+/// write strobe itself. This allows HAL peripherals like the ESP32 LCD_CAM, RP2040 PIO or
+/// STM32 FSMC to transfer data via DMA without blocking the executor.
+///
+/// Use [`ParallelInterface::new_async`] to create an interface for an async bus.
+pub trait AsyncOutputBus {
+    /// [u8] for 8-bit buses, [u16] for 16-bit buses, etc.
+    type Word: Copy;
+
+    /// Interface kind.
+    const KIND: InterfaceKind;
+
+    /// Error type
+    type Error: core::fmt::Debug;
+
+    /// Write a slice of words to the bus, toggling the write strobe for every word.
+    fn write_words(
+        &mut self,
+        words: &[Self::Word],
+    ) -> impl core::future::Future<Output = Result<(), Self::Error>>;
+}
+
+/// This trait represents the data pins of a parallel bus which can also be read.
+///
+/// Reading requires bidirectional data pins, which can't be expressed with the
+/// `embedded-hal` traits. This trait therefore has no generic implementation and
+/// must be implemented for the HAL specific pin types.
+pub trait InputBus: OutputBus {
+    /// Read the current value of the bus.
+    ///
+    /// Implementations are responsible for switching the data pins to inputs
+    /// for the read and back to outputs afterwards.
+    fn read_value(&mut self) -> Result<Self::Word, Self::Error>;
+}
+
 macro_rules! generic_bus {
     ($GenericxBitBus:ident { type Word = $Word:ident; const KIND: InterfaceKind = $InterfaceKind:expr; Pins {$($PX:ident => $x:tt,)*}}) => {
         /// A generic implementation of [OutputBus] using [OutputPin]s
@@ -118,6 +156,24 @@ generic_bus! {
     }
 }
 
+generic_bus! {
+    Generic9BitBus {
+        type Word = u16;
+        const KIND: InterfaceKind = InterfaceKind::Parallel9Bit;
+        Pins {
+            P0 => 0,
+            P1 => 1,
+            P2 => 2,
+            P3 => 3,
+            P4 => 4,
+            P5 => 5,
+            P6 => 6,
+            P7 => 7,
+            P8 => 8,
+        }
+    }
+}
+
 generic_bus! {
     Generic16BitBus {
         type Word = u16;
@@ -143,18 +199,66 @@ generic_bus! {
     }
 }
 
+generic_bus! {
+    Generic18BitBus {
+        type Word = u32;
+        const KIND: InterfaceKind = InterfaceKind::Parallel18Bit;
+        Pins {
+            P0 => 0,
+            P1 => 1,
+            P2 => 2,
+            P3 => 3,
+            P4 => 4,
+            P5 => 5,
+            P6 => 6,
+            P7 => 7,
+            P8 => 8,
+            P9 => 9,
+            P10 => 10,
+            P11 => 11,
+            P12 => 12,
+            P13 => 13,
+            P14 => 14,
+            P15 => 15,
+            P16 => 16,
+            P17 => 17,
+        }
+    }
+}
+
 /// Parallel interface error
+///
+/// The read pin error type `RD` defaults to [`Infallible`](core::convert::Infallible), which
+/// is used by interfaces without a read pin.
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub enum ParallelError<BUS, DC, WR> {
+pub enum ParallelError<BUS, DC, WR, RD = core::convert::Infallible> {
     /// Bus error
     Bus(BUS),
     /// Data/command pin error
     Dc(DC),
     /// Write pin error
     Wr(WR),
+    /// Read pin error
+    Rd(RD),
+}
+
+/// Marker type for no read pin.
+///
+/// A [`ParallelInterface`] without a read pin is write-only and doesn't implement
+/// [`ReadInterface`].
+pub struct NoReadPin;
+
+impl ErrorType for NoReadPin {
+    type Error = core::convert::Infallible;
 }
 
+/// Marker type for no write pin.
+///
+/// Used by a [`ParallelInterface`] on top of an [`AsyncOutputBus`], which generates the
+/// write strobe itself.
+pub struct NoWritePin;
+
 /// Parallel communication interface
 ///
 /// This interface implements a "8080" style write-only display interface using any
@@ -164,10 +268,18 @@ pub enum ParallelError<BUS, DC, WR> {
 /// All pins in the data bus are supposed to be high-active. High for the D/C pin meaning "data" and the
 /// write-enable being pulled low before the setting of the bits and supposed to be sampled at a
 /// low to high edge.
-pub struct ParallelInterface<BUS, DC, WR> {
+///
+/// HAL peripherals which generate the write strobe in hardware can be used by implementing
+/// [`AsyncOutputBus`] and creating the interface with [`new_async`](Self::new_async).
+///
+/// An optional read-enable pin can be added with [`with_read_pin`](Self::with_read_pin) to
+/// read data back from the display if the bus implements [`InputBus`]. The read pin must be
+/// high while the interface is idle.
+pub struct ParallelInterface<BUS, DC, WR, RD = NoReadPin> {
     bus: BUS,
     dc: DC,
     wr: WR,
+    rd: RD,
 }
 
 impl<BUS, DC, WR> ParallelInterface<BUS, DC, WR>
@@ -180,7 +292,12 @@ where
 {
     /// Create new parallel GPIO interface for communication with a display driver
     pub fn new(bus: BUS, dc: DC, wr: WR) -> Self {
-        Self { bus, dc, wr }
+        Self {
+            bus,
+            dc,
+            wr,
+            rd: NoReadPin,
+        }
     }
 
     /// Consume the display interface and return
@@ -189,18 +306,57 @@ where
         (self.bus, self.dc, self.wr)
     }
 
+    /// Adds a read-enable pin to the interface.
+    ///
+    /// The read pin is pulled low while a word is read from the bus.
+    pub fn with_read_pin<RD>(self, rd: RD) -> ParallelInterface<BUS, DC, WR, RD>
+    where
+        RD: OutputPin,
+    {
+        ParallelInterface {
+            bus: self.bus,
+            dc: self.dc,
+            wr: self.wr,
+            rd,
+        }
+    }
+}
+
+impl<BUS, DC, WR, RD> ParallelInterface<BUS, DC, WR, RD>
+where
+    BUS: OutputBus,
+    BUS::Word: From<u8> + Eq + core::ops::BitXor<Output = BUS::Word>,
+    DC: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+{
+    /// Consume the display interface and return
+    /// the bus and GPIO pins used by it, including the read pin
+    pub fn release(self) -> (BUS, DC, WR, RD) {
+        (self.bus, self.dc, self.wr, self.rd)
+    }
+}
+
+impl<BUS, DC, WR, RD> ParallelInterface<BUS, DC, WR, RD>
+where
+    BUS: OutputBus,
+    BUS::Word: From<u8> + Eq + core::ops::BitXor<Output = BUS::Word>,
+    DC: OutputPin,
+    WR: OutputPin,
+    RD: ErrorType,
+{
     /// Sends a single word to the display.
     fn send_word(
         &mut self,
         word: BUS::Word,
-    ) -> Result<(), ParallelError<BUS::Error, DC::Error, WR::Error>> {
+    ) -> Result<(), ParallelError<BUS::Error, DC::Error, WR::Error, RD::Error>> {
         self.wr.set_low().map_err(ParallelError::Wr)?;
         self.bus.set_value(word).map_err(ParallelError::Bus)?;
         self.wr.set_high().map_err(ParallelError::Wr)
     }
 }
 
-impl<BUS, DC, WR> Interface for ParallelInterface<BUS, DC, WR>
+impl<BUS, DC, WR, RD> Interface for ParallelInterface<BUS, DC, WR, RD>
 where
     BUS: OutputBus,
     // The Eq bound is used by the `set_value` optimization in the generic bus.
@@ -208,9 +364,10 @@ where
     BUS::Word: From<u8> + Eq + core::ops::BitXor<Output = BUS::Word>,
     DC: OutputPin,
     WR: OutputPin,
+    RD: ErrorType,
 {
     type Word = BUS::Word;
-    type Error = ParallelError<BUS::Error, DC::Error, WR::Error>;
+    type Error = ParallelError<BUS::Error, DC::Error, WR::Error, RD::Error>;
 
     const KIND: InterfaceKind = BUS::KIND;
 
@@ -236,4 +393,319 @@ where
         }
         Ok(())
     }
+
+    async fn send_repeated(&mut self, word: Self::Word, count: u32) -> Result<(), Self::Error> {
+        if count == 0 {
+            return Ok(());
+        }
+
+        // The bus only needs to be set once, every following word just toggles the write pin.
+        self.send_word(word)?;
+        for _ in 1..count {
+            self.wr.set_low().map_err(ParallelError::Wr)?;
+            self.wr.set_high().map_err(ParallelError::Wr)?;
+        }
+        Ok(())
+    }
+}
+
+impl<BUS, DC> ParallelInterface<BUS, DC, NoWritePin>
+where
+    BUS: AsyncOutputBus,
+    BUS::Word: From<u8>,
+    DC: OutputPin,
+{
+    /// Create new parallel interface for an async bus which generates the write strobe itself
+    pub fn new_async(bus: BUS, dc: DC) -> Self {
+        Self {
+            bus,
+            dc,
+            wr: NoWritePin,
+            rd: NoReadPin,
+        }
+    }
+
+    /// Consume the display interface and return
+    /// the bus and data/command pin used by it
+    pub fn release(self) -> (BUS, DC) {
+        (self.bus, self.dc)
+    }
+}
+
+impl<BUS, DC> Interface for ParallelInterface<BUS, DC, NoWritePin>
+where
+    BUS: AsyncOutputBus,
+    BUS::Word: From<u8>,
+    DC: OutputPin,
+{
+    type Word = BUS::Word;
+    type Error = ParallelError<BUS::Error, DC::Error, core::convert::Infallible>;
+
+    const KIND: InterfaceKind = BUS::KIND;
+
+    async fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        // Set DC pin low for command
+        self.dc.set_low().map_err(ParallelError::Dc)?;
+        self.bus
+            .write_words(&[BUS::Word::from(command)])
+            .await
+            .map_err(ParallelError::Bus)?;
+
+        // Set DC pin high for data
+        self.dc.set_high().map_err(ParallelError::Dc)?;
+
+        // Arguments need to be widened to the bus word, which is done in small chunks.
+        let mut buffer = [BUS::Word::from(0); 16];
+        for chunk in args.chunks(buffer.len()) {
+            for (word, &arg) in buffer.iter_mut().zip(chunk) {
+                *word = BUS::Word::from(arg);
+            }
+            self.bus
+                .write_words(&buffer[..chunk.len()])
+                .await
+                .map_err(ParallelError::Bus)?;
+        }
+
+        Ok(())
+    }
+
+    async fn send_data_slice(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        // DC pin is expected to be high (data mode) from a previous command.
+        self.bus.write_words(data).await.map_err(ParallelError::Bus)
+    }
+}
+
+impl<BUS, DC, WR, RD> ReadInterface for ParallelInterface<BUS, DC, WR, RD>
+where
+    BUS: InputBus,
+    BUS::Word: From<u8>
+        + Eq
+        + core::ops::BitXor<Output = BUS::Word>
+        + core::ops::BitAnd<Output = BUS::Word>,
+    u8: TryFrom<BUS::Word>,
+    DC: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+{
+    /// Send a command and read the response.
+    ///
+    /// The first read cycle after the command is always discarded as a dummy cycle, so
+    /// `dummy` is ignored.
+    async fn read_command(
+        &mut self,
+        command: u8,
+        _dummy: DummyCycles,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(ParallelError::Dc)?;
+        self.send_word(BUS::Word::from(command))?;
+        self.dc.set_high().map_err(ParallelError::Dc)?;
+
+        // The first read cycle after a command returns a dummy word.
+        self.read_word()?;
+        for byte in buffer.iter_mut() {
+            let word = self.read_word()? & BUS::Word::from(0xFF);
+            *byte = u8::try_from(word).unwrap_or(0);
+        }
+
+        Ok(())
+    }
+}
+
+impl<BUS, DC, WR, RD> ParallelInterface<BUS, DC, WR, RD>
+where
+    BUS: InputBus,
+    DC: OutputPin,
+    WR: OutputPin,
+    RD: OutputPin,
+{
+    /// Reads a single word from the display.
+    fn read_word(
+        &mut self,
+    ) -> Result<BUS::Word, ParallelError<BUS::Error, DC::Error, WR::Error, RD::Error>> {
+        self.rd.set_low().map_err(ParallelError::Rd)?;
+        let word = self.bus.read_value().map_err(ParallelError::Bus);
+        self.rd.set_high().map_err(ParallelError::Rd)?;
+        word
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+    use crate::_mock::MockOutputPin;
+
+    #[derive(Default)]
+    struct MockAsyncBus(Vec<Vec<u16>>);
+
+    impl AsyncOutputBus for MockAsyncBus {
+        type Word = u16;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+
+        async fn write_words(&mut self, words: &[u16]) -> Result<(), Self::Error> {
+            self.0.push(words.to_vec());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn async_bus_command() {
+        tokio_test::block_on(async {
+            let mut di = ParallelInterface::new_async(MockAsyncBus::default(), MockOutputPin);
+            di.send_command(0x2A, &[0x00, 0x00, 0x01, 0x3F])
+                .await
+                .unwrap();
+
+            let (bus, _) = di.release();
+            assert_eq!(bus.0, [vec![0x2A], vec![0x00, 0x00, 0x01, 0x3F]]);
+        });
+    }
+
+    #[test]
+    fn async_bus_writes_whole_slices() {
+        tokio_test::block_on(async {
+            let mut di = ParallelInterface::new_async(MockAsyncBus::default(), MockOutputPin);
+            let data: Vec<u16> = (0..1000).collect();
+            di.send_data_slice(&data).await.unwrap();
+
+            let (bus, _) = di.release();
+            assert_eq!(bus.0, [data]);
+        });
+    }
+
+    #[test]
+    fn async_bus_send_repeated() {
+        tokio_test::block_on(async {
+            let mut di = ParallelInterface::new_async(MockAsyncBus::default(), MockOutputPin);
+            di.send_repeated(0xF800, 1000).await.unwrap();
+
+            let (bus, _) = di.release();
+            assert_eq!(bus.0, [vec![0xF800; 512], vec![0xF800; 488]]);
+        });
+    }
+
+    #[derive(Default)]
+    struct CountingBus {
+        values: Vec<u16>,
+        /// Values returned by reads.
+        reads: Vec<u16>,
+    }
+
+    impl OutputBus for CountingBus {
+        type Word = u16;
+        type Error = core::convert::Infallible;
+
+        const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+
+        fn set_value(&mut self, value: Self::Word) -> Result<(), Self::Error> {
+            self.values.push(value);
+            Ok(())
+        }
+    }
+
+    impl InputBus for CountingBus {
+        fn read_value(&mut self) -> Result<Self::Word, Self::Error> {
+            Ok(self.reads.remove(0))
+        }
+    }
+
+    /// Pin with a different error type than the other pins, which always fails.
+    struct FailingPin;
+
+    impl embedded_hal::digital::ErrorType for FailingPin {
+        type Error = embedded_hal::digital::ErrorKind;
+    }
+
+    impl OutputPin for FailingPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Err(embedded_hal::digital::ErrorKind::Other)
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            Err(embedded_hal::digital::ErrorKind::Other)
+        }
+    }
+
+    #[derive(Default)]
+    struct CountingPin {
+        rising_edges: usize,
+        high: bool,
+    }
+
+    impl embedded_hal::digital::ErrorType for CountingPin {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for CountingPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.high = false;
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            if !self.high {
+                self.rising_edges += 1;
+            }
+            self.high = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn send_repeated_sets_bus_once() {
+        tokio_test::block_on(async {
+            let mut di = ParallelInterface::new(
+                CountingBus::default(),
+                MockOutputPin,
+                CountingPin::default(),
+            );
+            di.send_repeated(0x1234, 1000).await.unwrap();
+
+            let (bus, _, wr) = di.release();
+            assert_eq!(bus.values, [0x1234]);
+            assert_eq!(wr.rising_edges, 1000);
+        });
+    }
+
+    #[test]
+    fn read_discards_dummy_word() {
+        tokio_test::block_on(async {
+            let bus = CountingBus {
+                reads: vec![0xAA, 0x85, 0x85, 0x52],
+                ..Default::default()
+            };
+            let mut di = ParallelInterface::new(bus, MockOutputPin, CountingPin::default())
+                .with_read_pin(MockOutputPin);
+
+            let mut buffer = [0u8; 3];
+            di.read_command(0x04, DummyCycles::Clock, &mut buffer)
+                .await
+                .unwrap();
+
+            assert_eq!(buffer, [0x85, 0x85, 0x52]);
+            let (bus, _, _, _) = di.release();
+            assert_eq!(bus.values, [0x04]);
+        });
+    }
+
+    #[test]
+    fn read_pin_error() {
+        tokio_test::block_on(async {
+            let mut di =
+                ParallelInterface::new(CountingBus::default(), MockOutputPin, MockOutputPin)
+                    .with_read_pin(FailingPin);
+
+            let result = di.read_command(0x0A, DummyCycles::None, &mut [0u8]).await;
+            assert!(matches!(
+                result,
+                Err(ParallelError::Rd(embedded_hal::digital::ErrorKind::Other))
+            ));
+        });
+    }
 }
@@ -0,0 +1,241 @@
+//!
+//! Async Quad-SPI interface for MIPI DCS displays.
+//!
+//! AMOLED controllers such as the RM67162, SH8601, CO5300 or RM690B0 are commonly connected
+//! over QSPI. Every transfer starts with an instruction byte and a 24-bit address sent on a
+//! single data line. Commands use the `0x02` instruction with the command in the middle byte
+//! of the address and send their parameters on a single line. Pixel data uses the `0x32`
+//! instruction and is sent on all four data lines.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use lcd_async::interface::QspiInterface;
+//!
+//! let qspi = /* your QspiDevice implementation */;
+//! let mut iface = QspiInterface::new(qspi);
+//! // Use iface with the display driver
+//! ```
+
+use super::{Interface, InterfaceKind};
+
+/// Instruction for commands with parameters sent on a single data line.
+const WRITE_SINGLE: u8 = 0x02;
+/// Instruction for pixel data sent on four data lines.
+const WRITE_QUAD: u8 = 0x32;
+
+/// Write Memory Start
+const RAMWR: u8 = 0x2C;
+/// Write Memory Continue
+const RAMWRC: u8 = 0x3C;
+
+/// The QSPI transfers used by [`QspiInterface`].
+///
+/// This trait needs to be implemented for the QSPI peripheral of the HAL. Both methods
+/// transfer `instruction` and the 24 least significant bits of `address` (MSB first) on a
+/// single data line, with the chip select being asserted for the entire transfer.
+pub trait QspiDevice {
+    /// Error type
+    type Error: core::fmt::Debug;
+
+    /// Writes an instruction, an address and `data`, all on a single data line.
+    fn write_single(
+        &mut self,
+        instruction: u8,
+        address: u32,
+        data: &[u8],
+    ) -> impl core::future::Future<Output = Result<(), Self::Error>>;
+
+    /// Writes an instruction and an address on a single data line, followed by `data` on four data lines.
+    fn write_quad(
+        &mut self,
+        instruction: u8,
+        address: u32,
+        data: &[u8],
+    ) -> impl core::future::Future<Output = Result<(), Self::Error>>;
+}
+
+/// Async QSPI interface for MIPI DCS displays.
+///
+/// This struct implements the [`Interface`] trait on top of a [`QspiDevice`].
+///
+/// A Write Memory Start (`0x2C`) command without parameters isn't sent immediately, but is
+/// merged with the following pixel data into a single quad-wide transfer. Further calls to
+/// [`send_data_slice`](Interface::send_data_slice) use Write Memory Continue (`0x3C`). This
+/// makes [`Display::show_raw_data`](crate::Display::show_raw_data) use the quad-wide path
+/// without any changes to the models.
+///
+/// Use [`QspiInterface::new`] to construct, and [`QspiInterface::release`] to deconstruct and recover the QSPI resource.
+pub struct QspiInterface<QSPI> {
+    qspi: QSPI,
+    memory_write: Option<u8>,
+}
+
+impl<QSPI> QspiInterface<QSPI>
+where
+    QSPI: QspiDevice,
+{
+    /// Create a new async QSPI interface from a QSPI device.
+    pub fn new(qspi: QSPI) -> Self {
+        Self {
+            qspi,
+            memory_write: None,
+        }
+    }
+
+    /// Release the QSPI peripheral back, deconstructing the interface.
+    pub fn release(self) -> QSPI {
+        self.qspi
+    }
+}
+
+impl<QSPI> Interface for QspiInterface<QSPI>
+where
+    QSPI: QspiDevice,
+{
+    type Word = u8;
+    type Error = QSPI::Error;
+
+    const KIND: InterfaceKind = InterfaceKind::Qspi;
+
+    /// Send a command and its arguments to the display controller.
+    ///
+    /// Memory write commands without arguments are deferred until the pixel data is sent.
+    async fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        if let Some(pending) = self.memory_write.take() {
+            self.qspi
+                .write_single(WRITE_SINGLE, u32::from(pending) << 8, &[])
+                .await?;
+        }
+
+        if matches!(command, RAMWR | RAMWRC) && args.is_empty() {
+            self.memory_write = Some(command);
+            return Ok(());
+        }
+
+        self.qspi
+            .write_single(WRITE_SINGLE, u32::from(command) << 8, args)
+            .await
+    }
+
+    /// Send a slice of pixel data to the display controller using all four data lines.
+    async fn send_data_slice(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        let command = self.memory_write.take().unwrap_or(RAMWRC);
+        self.qspi
+            .write_quad(WRITE_QUAD, u32::from(command) << 8, data)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Transfer {
+        quad: bool,
+        instruction: u8,
+        address: u32,
+        data: Vec<u8>,
+    }
+
+    #[derive(Default)]
+    struct MockQspi(Vec<Transfer>);
+
+    impl QspiDevice for MockQspi {
+        type Error = core::convert::Infallible;
+
+        async fn write_single(
+            &mut self,
+            instruction: u8,
+            address: u32,
+            data: &[u8],
+        ) -> Result<(), Self::Error> {
+            self.0.push(Transfer {
+                quad: false,
+                instruction,
+                address,
+                data: data.to_vec(),
+            });
+            Ok(())
+        }
+
+        async fn write_quad(
+            &mut self,
+            instruction: u8,
+            address: u32,
+            data: &[u8],
+        ) -> Result<(), Self::Error> {
+            self.0.push(Transfer {
+                quad: true,
+                instruction,
+                address,
+                data: data.to_vec(),
+            });
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn commands_use_single_line() {
+        tokio_test::block_on(async {
+            let mut di = QspiInterface::new(MockQspi::default());
+            di.send_command(0x3A, &[0x55]).await.unwrap();
+
+            assert_eq!(
+                di.qspi.0,
+                [Transfer {
+                    quad: false,
+                    instruction: 0x02,
+                    address: 0x00_3A_00,
+                    data: vec![0x55],
+                }]
+            );
+        });
+    }
+
+    #[test]
+    fn memory_write_is_merged_with_pixel_data() {
+        tokio_test::block_on(async {
+            let mut di = QspiInterface::new(MockQspi::default());
+            di.send_command(0x2C, &[]).await.unwrap();
+            di.send_data_slice(&[1, 2]).await.unwrap();
+            di.send_data_slice(&[3, 4]).await.unwrap();
+
+            assert_eq!(
+                di.qspi.0,
+                [
+                    Transfer {
+                        quad: true,
+                        instruction: 0x32,
+                        address: 0x00_2C_00,
+                        data: vec![1, 2],
+                    },
+                    Transfer {
+                        quad: true,
+                        instruction: 0x32,
+                        address: 0x00_3C_00,
+                        data: vec![3, 4],
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn unused_memory_write_is_flushed() {
+        tokio_test::block_on(async {
+            let mut di = QspiInterface::new(MockQspi::default());
+            di.send_command(0x2C, &[]).await.unwrap();
+            di.send_command(0x29, &[]).await.unwrap();
+
+            assert_eq!(di.qspi.0.len(), 2);
+            assert_eq!(di.qspi.0[0].address, 0x00_2C_00);
+            assert_eq!(di.qspi.0[1].address, 0x00_29_00);
+        });
+    }
+}
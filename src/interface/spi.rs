@@ -5,6 +5,9 @@
 //! It is designed for use with async runtimes and drivers, and does not require an internal buffer—
 //! pixel data is sent directly from the provided slice.
 //!
+//! HALs which limit the size of a single DMA transfer can use [`SpiInterface::new_chunked`] to
+//! split large slices into multiple writes.
+//!
 //! # Example
 //!
 //! ```rust,ignore
@@ -19,9 +22,9 @@
 //! ```
 
 use embedded_hal::digital::OutputPin;
-use embedded_hal_async::spi::SpiDevice;
+use embedded_hal_async::spi::{Operation, SpiDevice};
 
-use super::{Interface, InterfaceKind};
+use super::{DummyCycles, Interface, InterfaceKind, ReadInterface};
 
 /// Error type for the async SPI interface.
 ///
@@ -45,8 +48,15 @@ pub enum SpiError<SPI, DC> {
 pub struct SpiInterface<SPI, DC> {
     spi: SPI,
     dc: DC,
+    max_chunk_size: usize,
 }
 
+/// The maximum number of chunks which are written in a single SPI transaction.
+///
+/// [`SpiDevice::transaction`] requires all operations of a transaction in one slice, which is
+/// kept on the stack to avoid an allocator.
+const CHUNKS_PER_TRANSACTION: usize = 16;
+
 impl<SPI, DC> SpiInterface<SPI, DC>
 where
     SPI: SpiDevice,
@@ -54,7 +64,36 @@ where
 {
     /// Create a new async SPI interface from an SPI device and DC pin.
     pub fn new(spi: SPI, dc: DC) -> Self {
-        Self { spi, dc }
+        Self {
+            spi,
+            dc,
+            max_chunk_size: usize::MAX,
+        }
+    }
+
+    /// Create a new async SPI interface which splits data into writes of at most `max_chunk_size` bytes.
+    ///
+    /// This is required for HALs which fail or truncate DMA transfers above a certain
+    /// size, e.g. 32 KB on the ESP32-C3 or 65535 bytes on STM32. Up to 16 chunks are
+    /// written in a single SPI transaction, so the chip select stays asserted between them.
+    ///
+    /// Slices larger than `16 * max_chunk_size` bytes are written in multiple transactions and
+    /// the chip select is released between them, in the middle of the memory write. The DC pin
+    /// stays high, but whether the controller continues the memory write after the chip select
+    /// is asserted again depends on the controller. If a whole frame needs to be sent in a
+    /// single chip select window, choose `max_chunk_size` so that the frame fits into 16 chunks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_chunk_size` is 0.
+    pub fn new_chunked(spi: SPI, dc: DC, max_chunk_size: usize) -> Self {
+        assert!(max_chunk_size > 0, "max_chunk_size must not be 0");
+
+        Self {
+            spi,
+            dc,
+            max_chunk_size,
+        }
     }
 
     /// Release the DC pin and SPI peripheral back, deconstructing the interface.
@@ -87,11 +126,270 @@ where
     /// Send a slice of pixel or data bytes to the display controller.
     ///
     /// The data is sent as-is over SPI, with the DC pin assumed to be high.
+    /// Slices larger than the maximum chunk size are split into multiple writes, with up to 16
+    /// writes per SPI transaction. See [`SpiInterface::new_chunked`].
     async fn send_data_slice(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
         // data is &[u8] because Self::Word = u8
         // Directly send the user's framebuffer slice.
-        // The underlying SPI driver might do its own buffering/chunking if necessary.
-        self.spi.write(data).await.map_err(SpiError::Spi)?;
+        if data.len() <= self.max_chunk_size {
+            self.spi.write(data).await.map_err(SpiError::Spi)?;
+            return Ok(());
+        }
+
+        let batch_size = self.max_chunk_size.saturating_mul(CHUNKS_PER_TRANSACTION);
+        for batch in data.chunks(batch_size) {
+            let mut chunks = batch.chunks(self.max_chunk_size);
+            let count = chunks.len();
+            let mut operations: [Operation<'_, u8>; CHUNKS_PER_TRANSACTION] =
+                core::array::from_fn(|_| Operation::Write(chunks.next().unwrap_or(&[])));
+
+            self.spi
+                .transaction(&mut operations[..count])
+                .await
+                .map_err(SpiError::Spi)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<SPI, DC> ReadInterface for SpiInterface<SPI, DC>
+where
+    SPI: SpiDevice,
+    DC: OutputPin,
+{
+    /// Send a command and read the response in the same SPI transaction.
+    ///
+    /// The response is clocked in on MISO, or on the shared SDA line for half-duplex
+    /// SPI devices. A dummy byte is read and discarded, a dummy clock cycle (e.g. for RDDID
+    /// or RDDST) is stripped by shifting the response before it is stored in `buffer`.
+    async fn read_command(
+        &mut self,
+        command: u8,
+        dummy: DummyCycles,
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.dc.set_low().map_err(SpiError::Dc)?;
+
+        match dummy {
+            DummyCycles::None => {
+                return self
+                    .spi
+                    .transaction(&mut [Operation::Write(&[command]), Operation::Read(buffer)])
+                    .await
+                    .map_err(SpiError::Spi);
+            }
+            DummyCycles::Byte => {
+                return self
+                    .spi
+                    .transaction(&mut [
+                        Operation::Write(&[command]),
+                        Operation::Read(&mut [0u8]),
+                        Operation::Read(buffer),
+                    ])
+                    .await
+                    .map_err(SpiError::Spi);
+            }
+            DummyCycles::Clock => {}
+        }
+
+        // Read one extra byte to receive the last bit, which is delayed by the dummy clock.
+        let mut last = [0u8];
+        self.spi
+            .transaction(&mut [
+                Operation::Write(&[command]),
+                Operation::Read(buffer),
+                Operation::Read(&mut last),
+            ])
+            .await
+            .map_err(SpiError::Spi)?;
+
+        let mut next = last[0];
+        for byte in buffer.iter_mut().rev() {
+            let current = *byte;
+            *byte = (current << 1) | (next >> 7);
+            next = current;
+        }
+
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{vec, vec::Vec};
+
+    use embedded_hal::digital;
+    use embedded_hal_async::spi::{self, Operation};
+
+    use super::*;
+
+    struct MockDc;
+
+    impl digital::ErrorType for MockDc {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for MockDc {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    /// SPI device that records writes and answers reads from a fixed response.
+    #[derive(Default)]
+    struct MockSpi {
+        written: Vec<Vec<u8>>,
+        response: Vec<u8>,
+        transactions: usize,
+        /// Number of operations in each transaction.
+        operations: Vec<usize>,
+        /// Writes larger than this are rejected, like a HAL with a DMA size limit.
+        max_write: Option<usize>,
+    }
+
+    impl spi::ErrorType for MockSpi {
+        type Error = spi::ErrorKind;
+    }
+
+    impl SpiDevice for MockSpi {
+        async fn transaction(
+            &mut self,
+            operations: &mut [Operation<'_, u8>],
+        ) -> Result<(), Self::Error> {
+            self.transactions += 1;
+            self.operations.push(operations.len());
+            let mut response = self.response.iter().copied();
+            for op in operations {
+                match op {
+                    Operation::Write(data)
+                        if self.max_write.is_some_and(|max| data.len() > max) =>
+                    {
+                        return Err(spi::ErrorKind::Other)
+                    }
+                    Operation::Write(data) => self.written.push(data.to_vec()),
+                    Operation::Read(buf) => buf.fill_with(|| response.next().unwrap_or(0)),
+                    _ => unimplemented!(),
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn read_single_byte_has_no_dummy_clock() {
+        tokio_test::block_on(async {
+            let spi = MockSpi {
+                response: vec![0x9C],
+                ..Default::default()
+            };
+            let mut di = SpiInterface::new(spi, MockDc);
+
+            let mut buffer = [0u8];
+            di.read_command(0x0A, DummyCycles::None, &mut buffer)
+                .await
+                .unwrap();
+
+            assert_eq!(buffer, [0x9C]);
+            assert_eq!(di.spi.written, [vec![0x0A]]);
+        });
+    }
+
+    #[test]
+    fn read_multiple_bytes_strips_dummy_clock() {
+        tokio_test::block_on(async {
+            // 0x85 0x85 0x52 shifted right by one dummy bit
+            let spi = MockSpi {
+                response: vec![0x42, 0xC2, 0xA9, 0x00],
+                ..Default::default()
+            };
+            let mut di = SpiInterface::new(spi, MockDc);
+
+            let mut buffer = [0u8; 3];
+            di.read_command(0x04, DummyCycles::Clock, &mut buffer)
+                .await
+                .unwrap();
+
+            assert_eq!(buffer, [0x85, 0x85, 0x52]);
+        });
+    }
+
+    #[test]
+    fn read_skips_dummy_byte() {
+        tokio_test::block_on(async {
+            // ILI9341 Read ID4: dummy byte, IC version, 0x93, 0x41
+            let spi = MockSpi {
+                response: vec![0xFF, 0x00, 0x93, 0x41],
+                ..Default::default()
+            };
+            let mut di = SpiInterface::new(spi, MockDc);
+
+            let mut buffer = [0u8; 3];
+            di.read_command(0xD3, DummyCycles::Byte, &mut buffer)
+                .await
+                .unwrap();
+
+            assert_eq!(buffer, [0x00, 0x93, 0x41]);
+        });
+    }
+
+    #[test]
+    fn unchunked_write_is_rejected_by_limited_device() {
+        tokio_test::block_on(async {
+            let spi = MockSpi {
+                max_write: Some(100),
+                ..Default::default()
+            };
+            let mut di = SpiInterface::new(spi, MockDc);
+
+            assert!(di.send_data_slice(&[0; 250]).await.is_err());
+        });
+    }
+
+    #[test]
+    fn chunked_write() {
+        tokio_test::block_on(async {
+            let spi = MockSpi {
+                max_write: Some(100),
+                ..Default::default()
+            };
+            let mut di = SpiInterface::new_chunked(spi, MockDc, 100);
+
+            let data: Vec<u8> = (0..250).map(|i| i as u8).collect();
+            di.send_data_slice(&data).await.unwrap();
+
+            assert_eq!(di.spi.transactions, 1);
+            assert_eq!(
+                di.spi.written.iter().map(Vec::len).collect::<Vec<_>>(),
+                [100, 100, 50]
+            );
+            assert_eq!(di.spi.written.concat(), data);
+        });
+    }
+
+    #[test]
+    fn chunked_write_uses_multiple_transactions() {
+        tokio_test::block_on(async {
+            let spi = MockSpi {
+                max_write: Some(10),
+                ..Default::default()
+            };
+            let mut di = SpiInterface::new_chunked(spi, MockDc, 10);
+
+            let data: Vec<u8> = (0..=255).collect();
+            di.send_data_slice(&data).await.unwrap();
+
+            // the chip select is released once, after the first 16 chunks
+            assert_eq!(di.spi.transactions, 2);
+            assert_eq!(di.spi.operations, [16, 10]);
+            assert_eq!(di.spi.written.len(), 26);
+            assert_eq!(di.spi.written.concat(), data);
+        });
+    }
+}
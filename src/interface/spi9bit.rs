@@ -0,0 +1,232 @@
+//!
+//! Async 3-wire (9-bit) SPI interface for MIPI DCS displays.
+//!
+//! Displays with a 3-line serial interface have no data/command pin. Instead every byte is
+//! preceded by a D/C bit, which results in 9-bit frames. This module packs these frames into
+//! a stream of bytes, so that a regular 8-bit [`SpiDevice`] can be used.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use lcd_async::interface::Spi9BitInterface;
+//!
+//! let spi = /* your async SPI device */;
+//! let mut iface = Spi9BitInterface::new(spi);
+//! // Use iface with the display driver
+//! ```
+
+use embedded_hal_async::spi::{Operation, SpiDevice};
+
+use super::{Interface, InterfaceKind};
+
+/// Number of 9-bit frames which are packed into one chunk.
+///
+/// Must be a multiple of 8, to ensure that only the last chunk of a transfer contains padding bits.
+const BUFFER_FRAMES: usize = 64;
+
+/// Maximum number of chunks written in a single SPI transaction.
+///
+/// [`SpiDevice::transaction`] requires all operations of a transaction in one slice, so the
+/// chunks are kept on the stack together.
+const CHUNKS_PER_TRANSACTION: usize = 16;
+
+/// Async 3-wire SPI interface for MIPI DCS displays without a data/command pin.
+///
+/// This struct implements the [`Interface`] trait for displays which expect the D/C bit as the
+/// first bit of a 9-bit frame. The frames are packed MSB first into bytes and written to the
+/// [`SpiDevice`] in chunks. If the number of frames in a transfer isn't a multiple of 8, the last
+/// byte is padded with zero bits, which the controller discards as an incomplete frame when the
+/// chip select is deasserted.
+///
+/// Up to 1024 frames are written in a single SPI transaction, so a command and its arguments are
+/// always sent in one chip select window. Data slices longer than 1024 bytes are written in
+/// multiple transactions and the chip select is released between them, in the middle of the
+/// memory write. Whether the controller continues the memory write after the chip select is
+/// asserted again depends on the controller.
+///
+/// Use [`Spi9BitInterface::new`] to construct, and [`Spi9BitInterface::release`] to deconstruct and recover the SPI resource.
+pub struct Spi9BitInterface<SPI> {
+    spi: SPI,
+}
+
+impl<SPI> Spi9BitInterface<SPI>
+where
+    SPI: SpiDevice,
+{
+    /// Create a new async 3-wire SPI interface from an SPI device.
+    pub fn new(spi: SPI) -> Self {
+        Self { spi }
+    }
+
+    /// Release the SPI peripheral back, deconstructing the interface.
+    pub fn release(self) -> SPI {
+        self.spi
+    }
+
+    /// Packs the frames and writes them to the SPI device.
+    async fn write_frames(&mut self, frames: impl Iterator<Item = u16>) -> Result<(), SPI::Error> {
+        let mut buffers = [[0u8; BUFFER_FRAMES * 9 / 8]; CHUNKS_PER_TRANSACTION];
+        let mut lens = [0usize; CHUNKS_PER_TRANSACTION];
+        let mut frames = frames.peekable();
+
+        while frames.peek().is_some() {
+            let mut count = 0;
+            for (buffer, len) in buffers.iter_mut().zip(lens.iter_mut()) {
+                if frames.peek().is_none() {
+                    break;
+                }
+                *len = pack_frames(frames.by_ref().take(BUFFER_FRAMES), buffer);
+                count += 1;
+            }
+
+            let mut chunks = buffers.iter().zip(lens).map(|(buffer, len)| &buffer[..len]);
+            let mut operations: [Operation<'_, u8>; CHUNKS_PER_TRANSACTION] =
+                core::array::from_fn(|_| Operation::Write(chunks.next().unwrap_or(&[])));
+
+            self.spi.transaction(&mut operations[..count]).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Packs 9-bit frames MSB first into `buffer` and returns the number of bytes used.
+fn pack_frames(frames: impl Iterator<Item = u16>, buffer: &mut [u8]) -> usize {
+    let mut acc = 0u32;
+    let mut bits = 0;
+    let mut len = 0;
+
+    for frame in frames {
+        acc = (acc << 9) | u32::from(frame & 0x1FF);
+        bits += 9;
+        while bits >= 8 {
+            bits -= 8;
+            buffer[len] = (acc >> bits) as u8;
+            len += 1;
+        }
+    }
+
+    if bits > 0 {
+        buffer[len] = (acc << (8 - bits)) as u8;
+        len += 1;
+    }
+
+    len
+}
+
+/// Returns the 9-bit frame for a command byte.
+fn command_frame(byte: u8) -> u16 {
+    u16::from(byte)
+}
+
+/// Returns the 9-bit frame for a data byte.
+fn data_frame(byte: u8) -> u16 {
+    0x100 | u16::from(byte)
+}
+
+impl<SPI> Interface for Spi9BitInterface<SPI>
+where
+    SPI: SpiDevice,
+{
+    type Word = u8;
+    type Error = SPI::Error;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial3Line;
+
+    /// Send a command and its arguments to the display controller.
+    ///
+    /// The D/C bit is cleared for the command byte and set for the argument bytes.
+    async fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        let frames =
+            core::iter::once(command_frame(command)).chain(args.iter().copied().map(data_frame));
+        self.write_frames(frames).await
+    }
+
+    /// Send a slice of pixel or data bytes to the display controller.
+    ///
+    /// Every byte is sent as a 9-bit frame with the D/C bit set.
+    async fn send_data_slice(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.write_frames(data.iter().copied().map(data_frame))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use embedded_hal_async::spi;
+
+    use super::*;
+
+    /// SPI device that records the number of bytes written in each transaction.
+    #[derive(Default)]
+    struct MockSpi {
+        transactions: Vec<usize>,
+    }
+
+    impl spi::ErrorType for MockSpi {
+        type Error = spi::ErrorKind;
+    }
+
+    impl SpiDevice for MockSpi {
+        async fn transaction(
+            &mut self,
+            operations: &mut [Operation<'_, u8>],
+        ) -> Result<(), Self::Error> {
+            let mut len = 0;
+            for op in operations {
+                match op {
+                    Operation::Write(data) => len += data.len(),
+                    _ => unimplemented!(),
+                }
+            }
+            self.transactions.push(len);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn command_is_sent_in_one_transaction() {
+        tokio_test::block_on(async {
+            let mut di = Spi9BitInterface::new(MockSpi::default());
+            di.send_command(0x2C, &[0; 200]).await.unwrap();
+
+            // 201 frames, 1809 bits
+            assert_eq!(di.spi.transactions, [227]);
+        });
+    }
+
+    #[test]
+    fn long_data_is_split_into_transactions() {
+        tokio_test::block_on(async {
+            let mut di = Spi9BitInterface::new(MockSpi::default());
+            di.send_data_slice(&[0; 1025]).await.unwrap();
+
+            assert_eq!(di.spi.transactions, [1152, 2]);
+        });
+    }
+
+    #[test]
+    fn packs_command_and_arguments() {
+        let mut buffer = [0u8; 4];
+        let frames = [command_frame(0x3A), data_frame(0x55)];
+
+        // 0_0011_1010 1_0101_0101 + 6 padding bits
+        assert_eq!(pack_frames(frames.into_iter(), &mut buffer), 3);
+        assert_eq!(buffer[..3], [0b0001_1101, 0b0101_0101, 0b0100_0000]);
+    }
+
+    #[test]
+    fn eight_frames_fill_nine_bytes() {
+        let mut buffer = [0u8; 9];
+
+        assert_eq!(
+            pack_frames(core::iter::repeat(data_frame(0xFF)).take(8), &mut buffer),
+            9
+        );
+        assert_eq!(buffer, [0xFF; 9]);
+    }
+}
@@ -0,0 +1,311 @@
+//!
+//! Async SPI bus interface with explicit chip select control.
+//!
+//! [`SpiInterface`](super::SpiInterface) uses a [`SpiDevice`](embedded_hal_async::spi::SpiDevice),
+//! which releases the chip select between separate writes. Some controllers require the command
+//! and its parameters to be sent in a single chip select window, which is provided by this
+//! interface on top of an exclusively owned [`SpiBus`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use lcd_async::interface::SpiBusInterface;
+//!
+//! let spi_bus = /* your async SPI bus */;
+//! let cs = /* your CS OutputPin */;
+//! let dc = /* your DC OutputPin */;
+//! let mut iface = SpiBusInterface::new(spi_bus, cs, dc);
+//! // Use iface with the display driver
+//!
+//! // Flushes the bus and deasserts CS before returning the resources
+//! let (spi_bus, cs, dc) = iface.release().await?;
+//! ```
+
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::spi::SpiBus;
+
+use super::{Interface, InterfaceKind};
+
+/// Write Memory Start
+const RAMWR: u8 = 0x2C;
+/// Write Memory Continue
+const RAMWRC: u8 = 0x3C;
+
+/// Error type for the async SPI bus interface.
+///
+/// Wraps errors from the SPI bus, the chip select (CS) pin or the data/command (DC) pin.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpiBusError<SPI, CS, DC> {
+    /// SPI bus error
+    Spi(SPI),
+    /// Chip select pin error
+    Cs(CS),
+    /// Data/command pin error
+    Dc(DC),
+}
+
+/// Async SPI bus interface for MIPI DCS displays.
+///
+/// This struct implements the [`Interface`] trait using an async [`SpiBus`], a chip select (CS)
+/// and a data/command (DC) output pin. The chip select is asserted at the start of every command
+/// and held for its parameters. After a memory write command (`0x2C` or `0x3C`) it stays asserted
+/// for the following pixel data, until [`flush`](Interface::flush) ends the memory write.
+/// [`Display`](crate::Display) calls `flush` after every memory write, code which sends pixel
+/// data through [`Display::dcs`](crate::Display::dcs) needs to call it explicitly.
+///
+/// The bus is flushed before the DC or CS pins are changed.
+///
+/// Use [`SpiBusInterface::new`] to construct, and [`SpiBusInterface::release`] to deconstruct and recover the SPI, CS and DC resources.
+pub struct SpiBusInterface<SPI, CS, DC> {
+    spi: SPI,
+    cs: CS,
+    dc: DC,
+    selected: bool,
+}
+
+impl<SPI, CS, DC> SpiBusInterface<SPI, CS, DC>
+where
+    SPI: SpiBus,
+    CS: OutputPin,
+    DC: OutputPin,
+{
+    /// Create a new async SPI bus interface from an SPI bus, CS pin and DC pin.
+    ///
+    /// The CS pin is expected to be high (deasserted).
+    pub fn new(spi: SPI, cs: CS, dc: DC) -> Self {
+        Self {
+            spi,
+            cs,
+            dc,
+            selected: false,
+        }
+    }
+
+    /// Release the SPI peripheral, CS and DC pins back, deconstructing the interface.
+    ///
+    /// A pending memory write is ended first: the bus is flushed and the CS pin is deasserted.
+    pub async fn release(
+        mut self,
+    ) -> Result<(SPI, CS, DC), SpiBusError<SPI::Error, CS::Error, DC::Error>> {
+        self.deselect().await?;
+        Ok((self.spi, self.cs, self.dc))
+    }
+
+    /// Asserts the chip select if it isn't already asserted.
+    fn select(&mut self) -> Result<(), SpiBusError<SPI::Error, CS::Error, DC::Error>> {
+        if !self.selected {
+            self.cs.set_low().map_err(SpiBusError::Cs)?;
+            self.selected = true;
+        }
+        Ok(())
+    }
+
+    /// Flushes the bus and deasserts the chip select if it is asserted.
+    async fn deselect(&mut self) -> Result<(), SpiBusError<SPI::Error, CS::Error, DC::Error>> {
+        if self.selected {
+            self.spi.flush().await.map_err(SpiBusError::Spi)?;
+            self.cs.set_high().map_err(SpiBusError::Cs)?;
+            self.selected = false;
+        }
+        Ok(())
+    }
+}
+
+impl<SPI, CS, DC> Interface for SpiBusInterface<SPI, CS, DC>
+where
+    SPI: SpiBus,
+    CS: OutputPin,
+    DC: OutputPin,
+{
+    type Word = u8;
+    type Error = SpiBusError<SPI::Error, CS::Error, DC::Error>;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    /// Send a command and its arguments to the display controller in a single chip select window.
+    ///
+    /// The chip select stays asserted after memory write commands.
+    async fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.deselect().await?;
+        self.select()?;
+
+        self.dc.set_low().map_err(SpiBusError::Dc)?;
+        self.spi.write(&[command]).await.map_err(SpiBusError::Spi)?;
+        self.spi.flush().await.map_err(SpiBusError::Spi)?;
+
+        self.dc.set_high().map_err(SpiBusError::Dc)?;
+        self.spi.write(args).await.map_err(SpiBusError::Spi)?;
+
+        if !matches!(command, RAMWR | RAMWRC) {
+            self.deselect().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Send a slice of pixel or data bytes to the display controller.
+    ///
+    /// The data is sent as-is over SPI, with the DC pin assumed to be high.
+    async fn send_data_slice(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.select()?;
+        self.spi.write(data).await.map_err(SpiBusError::Spi)
+    }
+
+    /// Flushes the bus and deasserts the chip select after a memory write.
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.deselect().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{cell::RefCell, rc::Rc, vec, vec::Vec};
+
+    use embedded_hal::digital;
+    use embedded_hal_async::spi;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Cs(bool),
+        Dc(bool),
+        Write(Vec<u8>),
+        Flush,
+    }
+
+    type Log = Rc<RefCell<Vec<Event>>>;
+
+    struct MockPin(Log, fn(bool) -> Event);
+
+    impl digital::ErrorType for MockPin {
+        type Error = core::convert::Infallible;
+    }
+
+    impl OutputPin for MockPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push((self.1)(false));
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push((self.1)(true));
+            Ok(())
+        }
+    }
+
+    struct MockBus(Log);
+
+    impl spi::ErrorType for MockBus {
+        type Error = core::convert::Infallible;
+    }
+
+    impl SpiBus for MockBus {
+        async fn read(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        async fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push(Event::Write(words.to_vec()));
+            Ok(())
+        }
+
+        async fn transfer(&mut self, _read: &mut [u8], _write: &[u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        async fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        async fn flush(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push(Event::Flush);
+            Ok(())
+        }
+    }
+
+    fn interface() -> (SpiBusInterface<MockBus, MockPin, MockPin>, Log) {
+        let log = Log::default();
+        let di = SpiBusInterface::new(
+            MockBus(log.clone()),
+            MockPin(log.clone(), Event::Cs),
+            MockPin(log.clone(), Event::Dc),
+        );
+        (di, log)
+    }
+
+    #[test]
+    fn command_and_args_in_one_window() {
+        tokio_test::block_on(async {
+            let (mut di, log) = interface();
+            di.send_command(0x2A, &[0x00, 0x10]).await.unwrap();
+
+            assert_eq!(
+                *log.borrow(),
+                [
+                    Event::Cs(false),
+                    Event::Dc(false),
+                    Event::Write(vec![0x2A]),
+                    Event::Flush,
+                    Event::Dc(true),
+                    Event::Write(vec![0x00, 0x10]),
+                    Event::Flush,
+                    Event::Cs(true),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn memory_write_keeps_window_open() {
+        tokio_test::block_on(async {
+            let (mut di, log) = interface();
+            di.send_command(0x2C, &[]).await.unwrap();
+            di.send_data_slice(&[1, 2, 3]).await.unwrap();
+            di.send_data_slice(&[4]).await.unwrap();
+            di.flush().await.unwrap();
+            di.send_command(0x29, &[]).await.unwrap();
+
+            assert_eq!(
+                *log.borrow(),
+                [
+                    Event::Cs(false),
+                    Event::Dc(false),
+                    Event::Write(vec![0x2C]),
+                    Event::Flush,
+                    Event::Dc(true),
+                    Event::Write(vec![]),
+                    Event::Write(vec![1, 2, 3]),
+                    Event::Write(vec![4]),
+                    Event::Flush,
+                    Event::Cs(true),
+                    Event::Cs(false),
+                    Event::Dc(false),
+                    Event::Write(vec![0x29]),
+                    Event::Flush,
+                    Event::Dc(true),
+                    Event::Write(vec![]),
+                    Event::Flush,
+                    Event::Cs(true),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn release_ends_memory_write() {
+        tokio_test::block_on(async {
+            let (mut di, log) = interface();
+            di.send_command(0x2C, &[]).await.unwrap();
+            di.send_data_slice(&[1]).await.unwrap();
+            log.borrow_mut().clear();
+
+            let _ = di.release().await.unwrap();
+
+            assert_eq!(*log.borrow(), [Event::Flush, Event::Cs(true)]);
+        });
+    }
+}
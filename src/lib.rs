@@ -14,10 +14,12 @@
 //!
 //! - **Fully Asynchronous:** All display I/O is performed via async traits, making it ideal for async runtimes and DMA-driven workflows.
 //! - **Framebuffer-Based Drawing:** Draw your scene into an in-memory buffer using `embedded-graphics`, then send the entire frame to the display in one efficient async operation.
-//! - **Separation of Concerns:** Drawing is synchronous and CPU-bound; sending to the display is async and I/O-bound. This enables double buffering and advanced rendering patterns.
+//! - **Separation of Concerns:** Drawing is synchronous and CPU-bound; sending to the display is async and I/O-bound. This enables double buffering ([`swap_chain::SwapChain`]) and advanced rendering patterns.
 //! - **Multiple Interface Support:**
-//!   - SPI ([`interface::SpiInterface`])
-//!   - 8080-style parallel via GPIO ([`interface::ParallelInterface`])
+//!   - SPI ([`interface::SpiInterface`], or [`interface::SpiBusInterface`] with explicit chip select control)
+//!   - 3-wire 9-bit SPI without a DC pin ([`interface::Spi9BitInterface`])
+//!   - Quad-SPI for AMOLED controllers ([`interface::QspiInterface`])
+//!   - 8080-style parallel via GPIO or an async DMA-capable bus ([`interface::ParallelInterface`])
 //!
 //! ## Supported Models
 //!
@@ -62,12 +64,17 @@
 //!
 //! Licensed under MIT, same as the original mipidsi crate.
 
-use dcs::SetAddressMode;
+use dcs::{InterfaceExt, ReadInterfaceExt, SetAddressMode};
 
 pub mod interface;
+use interface::PixelWord;
 
+use embedded_graphics_core::{
+    geometry::{Dimensions, Point, Size},
+    primitives::Rectangle,
+};
 use embedded_hal::digital::OutputPin;
-use embedded_hal_async::delay::DelayNs;
+use embedded_hal_async::digital::Wait;
 
 pub mod options;
 use options::MemoryMapping;
@@ -75,14 +82,36 @@ use options::MemoryMapping;
 mod builder;
 pub use builder::*;
 
+mod any_display;
+pub use any_display::*;
+
+mod vsync;
+pub use vsync::*;
+
+mod sleep;
+pub use sleep::*;
+
+mod banded;
+
+pub mod backlight;
+use backlight::{Backlight, NoBacklight};
+
 pub mod dcs;
 
 pub mod models;
 pub mod raw_framebuf;
+pub mod swap_chain;
+pub mod terminal;
 use models::Model;
+use raw_framebuf::IntoRawBytes;
 
 mod graphics;
 
+#[cfg(any(test, feature = "testing"))]
+extern crate alloc;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
 mod test_image;
 pub use test_image::TestImage;
 
@@ -91,7 +120,7 @@ pub mod _troubleshooting;
 ///
 /// Display driver to connect to TFT displays.
 ///
-pub struct Display<DI, MODEL, RST>
+pub struct Display<DI, MODEL, RST, TE = NoTePin, BL = NoBacklight>
 where
     DI: interface::Interface,
     MODEL: Model,
@@ -103,16 +132,20 @@ where
     model: MODEL,
     // Reset pin
     rst: Option<RST>,
+    // Tearing effect input pin
+    te: TE,
+    // Backlight control
+    backlight: BL,
+    // Backlight level while the display is awake
+    backlight_level: u8,
     // Model Options, includes current orientation
     options: options::ModelOptions,
     // Current MADCTL value copy for runtime updates
     #[allow(dead_code)]
     madctl: SetAddressMode,
-    // State monitor for sleeping TODO: refactor to a Model-connected state machine
-    sleeping: bool,
 }
 
-impl<DI, M, RST> Display<DI, M, RST>
+impl<DI, M, RST, TE, BL> Display<DI, M, RST, TE, BL>
 where
     DI: interface::Interface,
     M: Model,
@@ -164,7 +197,250 @@ where
 
         self.set_address_window(x, y, ex, ey).await?;
         M::write_memory_start(&mut self.di).await?;
-        self.di.send_data_slice(pixel_data).await
+        self.di.send_data_slice(pixel_data).await?;
+        self.di.flush().await
+    }
+
+    /// Sends raw pixel bytes to the specified rectangular region of the display.
+    ///
+    /// Unlike [`show_raw_data`](Self::show_raw_data), the pixel data is always a byte slice
+    /// in the [`IntoRawBytes`] format of the model's color format,
+    /// e.g. the buffer of a [`RawFrameBuf`](raw_framebuf::RawFrameBuf). The bytes are packed
+    /// into interface words as required by the interface kind, which allows framebuffers to be
+    /// sent over 9, 16 and 18 bit parallel interfaces. On 16 bit interfaces the big-endian
+    /// byte pairs of 16 bit colors, like [`Rgb565`](embedded_graphics_core::pixelcolor::Rgb565),
+    /// are sent as one word.
+    ///
+    /// The packing is done in small chunks on the stack. Byte wide interfaces don't require
+    /// packing and the data is sent without copying it, like
+    /// [`show_raw_data`](Self::show_raw_data) would.
+    pub async fn show_raw_bytes(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        pixel_data: &[u8],
+    ) -> Result<(), DI::Error>
+    where
+        DI::Word: PixelWord,
+        M::ColorFormat: IntoRawBytes,
+    {
+        let ex = x + width - 1;
+        let ey = y + height - 1;
+
+        self.set_address_window(x, y, ex, ey).await?;
+        M::write_memory_start(&mut self.di).await?;
+        self.send_raw_bytes(pixel_data).await?;
+        self.di.flush().await
+    }
+
+    /// Sends raw pixel bytes as part of a memory write, packed into interface words.
+    async fn send_raw_bytes(&mut self, pixel_data: &[u8]) -> Result<(), DI::Error>
+    where
+        DI::Word: PixelWord,
+        M::ColorFormat: IntoRawBytes,
+    {
+        if let Some(words) = DI::Word::as_words(pixel_data) {
+            return self.di.send_data_slice(words).await;
+        }
+
+        let bytes_per_pixel = M::ColorFormat::BYTES_PER_PIXEL;
+        let mut buffer = [DI::Word::default(); 128];
+        let mut len = 0;
+        let mut pixel_words = [DI::Word::default(); 3];
+        for pixel in pixel_data.chunks_exact(bytes_per_pixel) {
+            let count = DI::Word::pack_pixel(DI::KIND, pixel, &mut pixel_words);
+            if len + count > buffer.len() {
+                self.di.send_data_slice(&buffer[..len]).await?;
+                len = 0;
+            }
+            buffer[len..len + count].copy_from_slice(&pixel_words[..count]);
+            len += count;
+        }
+
+        if len > 0 {
+            self.di.send_data_slice(&buffer[..len]).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Sends a rectangular region of a framebuffer to the same region of the display.
+    ///
+    /// The framebuffer is expected to cover the display starting at the top left corner. The
+    /// address window is set to `area` and the rows of the region are sent directly out of the
+    /// framebuffer, using a single memory write. The area is clipped to the framebuffer size.
+    ///
+    /// The pixels are packed into interface words like in
+    /// [`show_raw_bytes`](Self::show_raw_bytes), byte wide interfaces send them without copying.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{prelude::*, primitives::Rectangle};
+    /// use lcd_async::raw_framebuf::RawFrameBuf;
+    ///
+    /// # tokio_test::block_on(async {
+    /// # let mut display = lcd_async::_mock::new_mock_display().await;
+    /// let mut buffer = [0u8; 320 * 240 * 2];
+    /// let fbuf = RawFrameBuf::new(&mut buffer[..], 320, 240);
+    ///
+    /// // update a 40x20 widget at (100, 50)
+    /// let widget = Rectangle::new(Point::new(100, 50), Size::new(40, 20));
+    /// display.show_region(&fbuf, widget).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn show_region<BUF>(
+        &mut self,
+        framebuffer: &raw_framebuf::RawFrameBuf<M::ColorFormat, BUF>,
+        area: Rectangle,
+    ) -> Result<(), DI::Error>
+    where
+        DI::Word: PixelWord,
+        M::ColorFormat: IntoRawBytes,
+        BUF: raw_framebuf::RawBufferBackendMut,
+    {
+        let area = area.intersection(&framebuffer.bounding_box());
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        let (x, y) = (area.top_left.x as u16, area.top_left.y as u16);
+        self.set_address_window(x, y, bottom_right.x as u16, bottom_right.y as u16)
+            .await?;
+        M::write_memory_start(&mut self.di).await?;
+
+        let bytes_per_pixel = M::ColorFormat::BYTES_PER_PIXEL;
+        let stride = framebuffer.width() * bytes_per_pixel;
+        let row_len = area.size.width as usize * bytes_per_pixel;
+        let start = usize::from(y) * stride + usize::from(x) * bytes_per_pixel;
+        let bytes = framebuffer.as_bytes();
+
+        if row_len == stride {
+            // full width regions are contiguous in the framebuffer
+            let end = start + area.size.height as usize * stride;
+            self.send_raw_bytes(&bytes[start..end]).await?;
+        } else {
+            for row in 0..area.size.height as usize {
+                let row_start = start + row * stride;
+                self.send_raw_bytes(&bytes[row_start..row_start + row_len])
+                    .await?;
+            }
+        }
+
+        self.di.flush().await
+    }
+
+    /// Sends the changed area of a framebuffer to the display.
+    ///
+    /// The area returned by [`RawFrameBuf::take_dirty`](raw_framebuf::RawFrameBuf::take_dirty)
+    /// is sent with [`show_region`](Self::show_region). Nothing is sent if the framebuffer
+    /// wasn't changed since the last call. Without dirty tracking enabled on the framebuffer,
+    /// the whole frame is sent.
+    pub async fn show_dirty<BUF>(
+        &mut self,
+        framebuffer: &mut raw_framebuf::RawFrameBuf<M::ColorFormat, BUF>,
+    ) -> Result<(), DI::Error>
+    where
+        DI::Word: PixelWord,
+        M::ColorFormat: IntoRawBytes,
+        BUF: raw_framebuf::RawBufferBackendMut,
+    {
+        match framebuffer.take_dirty() {
+            Some(area) => self.show_region(framebuffer, area).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Fills a rectangular area of the display with a single color.
+    ///
+    /// The address window is set once and the color is streamed to the display from a small
+    /// buffer on the stack, without requiring a framebuffer. If the color fits into a single
+    /// interface word, e.g. [`Rgb565`](embedded_graphics_core::pixelcolor::Rgb565) on a 16 bit
+    /// parallel interface, it is sent with
+    /// [`Interface::send_repeated`](interface::Interface::send_repeated). The area is clipped to
+    /// the display size in the current orientation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # let mut display = lcd_async::_mock::new_mock_display().await;
+    /// let area = Rectangle::new(Point::new(10, 20), Size::new(100, 50));
+    /// display.fill_rect(area, Rgb565::BLUE).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn fill_rect(
+        &mut self,
+        area: Rectangle,
+        color: M::ColorFormat,
+    ) -> Result<(), DI::Error>
+    where
+        DI::Word: PixelWord,
+        M::ColorFormat: IntoRawBytes,
+    {
+        let (width, height) = self.options.display_size();
+        let area = area.intersection(&Rectangle::new(
+            Point::zero(),
+            Size::new(width.into(), height.into()),
+        ));
+        let Some(bottom_right) = area.bottom_right() else {
+            return Ok(());
+        };
+
+        self.set_address_window(
+            area.top_left.x as u16,
+            area.top_left.y as u16,
+            bottom_right.x as u16,
+            bottom_right.y as u16,
+        )
+        .await?;
+        M::write_memory_start(&mut self.di).await?;
+
+        let mut pixel_words = [DI::Word::default(); 3];
+        let raw = color.into_raw_bytes();
+        let count = DI::Word::pack_pixel(DI::KIND, raw.as_ref(), &mut pixel_words);
+        let pixels = area.size.width * area.size.height;
+
+        let pixel_words = &pixel_words[..count];
+        if pixel_words.iter().all(|&word| word == pixel_words[0]) {
+            self.di
+                .send_repeated(pixel_words[0], pixels * count as u32)
+                .await?;
+            return self.di.flush().await;
+        }
+
+        // Colors which need multiple different words are sent as a repeated pattern.
+        let mut buffer = [DI::Word::default(); interface::REPEAT_BUFFER_LEN];
+        let pixels_per_chunk = buffer.len() / count;
+        for chunk in buffer[..pixels_per_chunk * count].chunks_exact_mut(count) {
+            chunk.copy_from_slice(pixel_words);
+        }
+
+        let mut remaining = pixels as usize;
+        while remaining > 0 {
+            let len = remaining.min(pixels_per_chunk);
+            self.di.send_data_slice(&buffer[..len * count]).await?;
+            remaining -= len;
+        }
+
+        self.di.flush().await
+    }
+
+    /// Fills the whole display with a single color.
+    ///
+    /// See [`fill_rect`](Self::fill_rect) for details.
+    pub async fn clear(&mut self, color: M::ColorFormat) -> Result<(), DI::Error>
+    where
+        DI::Word: PixelWord,
+        M::ColorFormat: IntoRawBytes,
+    {
+        let (width, height) = self.options.display_size();
+        let area = Rectangle::new(Point::zero(), Size::new(width.into(), height.into()));
+        self.fill_rect(area, color).await
     }
 
     /// Sets the vertical scroll region.
@@ -201,14 +477,6 @@ where
         M::set_vertical_scroll_offset(&mut self.di, offset).await
     }
 
-    ///
-    /// Release resources allocated to this driver back.
-    /// This returns the display interface, reset pin and and the model deconstructing the driver.
-    ///
-    pub fn release(self) -> (DI, M, Option<RST>) {
-        (self.di, self.model, self.rst)
-    }
-
     // Sets the address window for the display.
     async fn set_address_window(
         &mut self,
@@ -243,6 +511,39 @@ where
         .await
     }
 
+    /// Reads the display identification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// # let mut display = lcd_async::_mock::new_mock_display().await;
+    /// let id = display.read_id().await.unwrap();
+    /// # });
+    /// ```
+    pub async fn read_id(&mut self) -> Result<dcs::DisplayId, DI::Error>
+    where
+        DI: interface::ReadInterface,
+    {
+        self.di.read_command_response(dcs::ReadDisplayId).await
+    }
+
+    /// Reads the display status.
+    pub async fn read_status(&mut self) -> Result<dcs::DisplayStatus, DI::Error>
+    where
+        DI: interface::ReadInterface,
+    {
+        self.di.read_command_response(dcs::ReadDisplayStatus).await
+    }
+
+    /// Reads the display power mode.
+    pub async fn read_power_mode(&mut self) -> Result<dcs::PowerMode, DI::Error>
+    where
+        DI: interface::ReadInterface,
+    {
+        self.di.read_command_response(dcs::ReadPowerMode).await
+    }
+
     ///
     /// Configures the tearing effect output.
     ///
@@ -253,32 +554,155 @@ where
         M::set_tearing_effect(&mut self.di, tearing_effect, &self.options).await
     }
 
+    /// Sets the scanline at which the tearing effect signal is generated.
     ///
-    /// Returns `true` if display is currently set to sleep.
+    /// Together with [`read_scanline`](Self::read_scanline) this can be used for beam racing:
+    /// by scheduling the tearing effect signal in the middle of the screen, the top half can
+    /// be updated while the controller refreshes the bottom half, which allows tearing-free
+    /// updates even if a whole frame can't be sent within one refresh period.
     ///
-    pub fn is_sleeping(&self) -> bool {
-        self.sleeping
+    /// Scanlines are counted in the default orientation of the display and the tearing effect
+    /// output needs to be enabled with [`set_tearing_effect`](Self::set_tearing_effect).
+    pub async fn set_tear_scanline(&mut self, scanline: u16) -> Result<(), DI::Error> {
+        self.di
+            .write_command(dcs::SetTearScanline::new(scanline))
+            .await
     }
 
+    /// Reads the scanline which is currently refreshed by the display controller.
+    pub async fn read_scanline(&mut self) -> Result<u16, DI::Error>
+    where
+        DI: interface::ReadInterface,
+    {
+        self.di.read_command_response(dcs::GetScanline).await
+    }
+
+    /// Sets the display brightness.
+    ///
+    /// The brightness ranges from `0` (darkest) to `255` (brightest). On LCD controllers this
+    /// controls the PWM output for the backlight driver, which needs to be connected to the
+    /// backlight for this to have an effect. AMOLED controllers set the brightness of the
+    /// panel directly.
+    pub async fn set_brightness(&mut self, brightness: u8) -> Result<(), DI::Error> {
+        M::set_brightness(&mut self.di, brightness).await
+    }
+
+    /// Sets the content adaptive brightness control (CABC) mode.
+    pub async fn set_cabc_mode(&mut self, mode: options::CabcMode) -> Result<(), DI::Error> {
+        M::set_cabc_mode(&mut self.di, mode).await
+    }
+
+    /// Sets the gamma curve.
+    ///
+    /// Setting [`GammaCurve::Default`](options::GammaCurve::Default) doesn't restore the gamma
+    /// curve set by the init sequence of the model, it leaves the current gamma curve unchanged.
+    ///
+    /// Returns [`ConfigurationError::UnsupportedGamma`] if the model doesn't support the gamma
+    /// curve.
+    ///
+    /// # Examples
     ///
-    /// Puts the display to sleep, reducing power consumption.
-    /// Need to call [Self::wake] before issuing other commands
+    /// ```
+    /// use lcd_async::options::{GammaCurve, PredefinedGamma};
     ///
-    pub async fn sleep<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
-        M::sleep(&mut self.di, delay).await?;
-        self.sleeping = true;
+    /// # tokio_test::block_on(async {
+    /// # let mut display = lcd_async::_mock::new_mock_display().await;
+    /// display
+    ///     .set_gamma(GammaCurve::Predefined(PredefinedGamma::Curve2))
+    ///     .await
+    ///     .unwrap();
+    /// # });
+    /// ```
+    pub async fn set_gamma(
+        &mut self,
+        gamma: options::GammaCurve,
+    ) -> Result<(), DisplayError<DI::Error>> {
+        M::validate_gamma(gamma).map_err(DisplayError::InvalidConfiguration)?;
+        M::set_gamma(&mut self.di, gamma)
+            .await
+            .map_err(DisplayError::Interface)?;
+        self.options.gamma = gamma;
         Ok(())
     }
 
+    /// Sets the frame rate and porches.
+    ///
+    /// Returns [`ConfigurationError::UnsupportedFrameRate`] if the model doesn't support
+    /// setting the frame rate or the settings are out of range. See
+    /// [`FrameRate`](options::FrameRate) for the supported models.
     ///
-    /// Wakes the display after it's been set to sleep via [Self::sleep]
+    /// # Examples
+    ///
+    /// ```
+    /// use lcd_async::options::FrameRate;
     ///
-    pub async fn wake<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), DI::Error> {
-        M::wake(&mut self.di, delay).await?;
-        self.sleeping = false;
+    /// # tokio_test::block_on(async {
+    /// # let mut display = lcd_async::_mock::new_mock_display().await;
+    /// // ILI9341: lower the frame rate from 70 Hz to about 61 Hz
+    /// display.set_frame_rate(FrameRate::new(0x1F)).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn set_frame_rate(
+        &mut self,
+        frame_rate: options::FrameRate,
+    ) -> Result<(), DisplayError<DI::Error>> {
+        M::validate_frame_rate(frame_rate).map_err(DisplayError::InvalidConfiguration)?;
+        M::set_frame_rate(&mut self.di, frame_rate)
+            .await
+            .map_err(DisplayError::Interface)?;
+        self.options.frame_rate = Some(frame_rate);
         Ok(())
     }
 
+    /// Enters partial mode, in which only a band of rows is driven by the controller.
+    ///
+    /// The `rows` are given in the default orientation of the display and must be a non-empty
+    /// range inside the display size. Rows outside of the partial area are shown in the
+    /// non-display color of the controller, which is usually black, and aren't refreshed.
+    /// Combined with [`EnterIdleMode`](dcs::EnterIdleMode) this reduces the power consumption,
+    /// e.g. for an always-on watch face.
+    ///
+    /// Returns [`ConfigurationError::InvalidPartialArea`] if the rows are empty or extend
+    /// beyond the display.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// # let mut display = lcd_async::_mock::new_mock_display().await;
+    /// // only drive the rows 100 to 139
+    /// display.enter_partial_mode(100..140).await.unwrap();
+    /// # });
+    /// ```
+    pub async fn enter_partial_mode(
+        &mut self,
+        rows: core::ops::Range<u16>,
+    ) -> Result<(), DisplayError<DI::Error>> {
+        if rows.is_empty() || rows.end > self.options.display_size.1 {
+            return Err(DisplayError::InvalidConfiguration(
+                ConfigurationError::InvalidPartialArea,
+            ));
+        }
+
+        let offset = self.options.display_offset.1;
+        self.di
+            .write_command(dcs::SetPartialRows::new(
+                rows.start + offset,
+                rows.end - 1 + offset,
+            ))
+            .await
+            .map_err(DisplayError::Interface)?;
+        self.di
+            .write_command(dcs::EnterPartialMode)
+            .await
+            .map_err(DisplayError::Interface)
+    }
+
+    /// Leaves partial mode and drives all rows of the display again.
+    pub async fn exit_partial_mode(&mut self) -> Result<(), DI::Error> {
+        self.di.write_command(dcs::EnterNormalMode).await
+    }
+
     /// Returns the DCS interface for sending raw commands.
     ///
     /// # Safety
@@ -292,6 +716,115 @@ where
     }
 }
 
+impl<DI, M, RST, TE, BL> Display<DI, M, RST, TE, BL>
+where
+    DI: interface::Interface,
+    M: Model,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Sets the backlight level.
+    ///
+    /// The level ranges from `0` (off) to `255` (full brightness), see [`Backlight::set_level`].
+    pub fn set_backlight(&mut self, level: u8) -> Result<(), BL::Error> {
+        self.backlight_level = level;
+        self.backlight.set_level(level)
+    }
+
+    /// Returns the backlight level.
+    pub fn backlight(&self) -> u8 {
+        self.backlight_level
+    }
+
+    /// Removes the backlight from the display driver and returns it.
+    ///
+    /// The backlight is left in its current state. This can be used to
+    /// [`release`](Self::release) a display with a backlight.
+    pub fn release_backlight(self) -> (Display<DI, M, RST, TE>, BL) {
+        let display = Display {
+            di: self.di,
+            model: self.model,
+            rst: self.rst,
+            te: self.te,
+            backlight: NoBacklight,
+            backlight_level: self.backlight_level,
+            options: self.options,
+            madctl: self.madctl,
+        };
+
+        (display, self.backlight)
+    }
+}
+
+impl<DI, M, RST> Display<DI, M, RST, NoTePin>
+where
+    DI: interface::Interface,
+    M: Model,
+    RST: OutputPin,
+{
+    ///
+    /// Release resources allocated to this driver back.
+    /// This returns the display interface, reset pin and and the model deconstructing the driver.
+    ///
+    /// A display with a backlight is released in two steps, the backlight is removed first:
+    ///
+    /// ```
+    /// # tokio_test::block_on(async {
+    /// # let di = lcd_async::_mock::MockDisplayInterface;
+    /// # let bl = lcd_async::_mock::MockOutputPin;
+    /// # let mut delay = lcd_async::_mock::MockDelay;
+    /// # let display = lcd_async::Builder::new(lcd_async::models::ILI9341Rgb565, di)
+    /// #     .backlight(lcd_async::backlight::BacklightPin::new(bl))
+    /// #     .init(&mut delay)
+    /// #     .await
+    /// #     .unwrap();
+    /// let (display, backlight) = display.release_backlight();
+    /// let (di, model, rst) = display.release();
+    /// # });
+    /// ```
+    pub fn release(self) -> (DI, M, Option<RST>) {
+        (self.di, self.model, self.rst)
+    }
+}
+
+impl<DI, M, RST, TE> Display<DI, M, RST, TE>
+where
+    DI: interface::Interface,
+    M: Model,
+    RST: OutputPin,
+    TE: Wait,
+{
+    ///
+    /// Release resources allocated to this driver back.
+    /// This returns the display interface, reset pin, the model and the tearing effect pin deconstructing the driver.
+    ///
+    /// A display with a backlight needs to be released with
+    /// [`release_backlight`](Display::release_backlight) first.
+    ///
+    pub fn release(self) -> (DI, M, Option<RST>, TE) {
+        (self.di, self.model, self.rst, self.te)
+    }
+}
+
+/// Error returned by [`Display`] methods which validate their arguments or control the
+/// backlight.
+#[non_exhaustive]
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DisplayError<DI, BL = core::convert::Infallible> {
+    /// Error caused by the display interface.
+    Interface(DI),
+
+    /// Error caused by the [`Backlight`] implementation.
+    Backlight(BL),
+
+    /// Invalid configuration error.
+    ///
+    /// The arguments aren't valid for the display, e.g. an area which extends beyond the
+    /// display size.
+    InvalidConfiguration(ConfigurationError),
+}
+
 /// Mock implementations of embedded-hal and interface traits for async architecture.
 ///
 /// Do not use types in this module outside of doc tests.
@@ -303,7 +836,7 @@ pub mod _mock {
     use embedded_hal_async::delay::DelayNs;
 
     use crate::{
-        interface::{Interface, InterfaceKind},
+        interface::{DummyCycles, Interface, InterfaceKind, ReadInterface},
         models::ILI9341Rgb565,
         Builder, Display, NoResetPin,
     };
@@ -315,6 +848,34 @@ pub mod _mock {
             .unwrap()
     }
 
+    pub struct MockTePin;
+
+    impl digital::ErrorType for MockTePin {
+        type Error = core::convert::Infallible;
+    }
+
+    impl embedded_hal_async::digital::Wait for MockTePin {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
     pub struct MockOutputPin;
 
     impl digital::OutputPin for MockOutputPin {
@@ -368,4 +929,730 @@ pub mod _mock {
             Ok(())
         }
     }
+
+    impl ReadInterface for MockDisplayInterface {
+        async fn read_command(
+            &mut self,
+            _command: u8,
+            _dummy: DummyCycles,
+            buffer: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            buffer.fill(0);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+
+    use embedded_graphics::{pixelcolor::Rgb565, prelude::*, primitives::Rectangle};
+
+    use crate::{
+        backlight::Backlight,
+        models::{ILI9341Rgb565, GC9A01, RM67162, ST7789},
+        options::{CabcMode, FrameRate, GammaCurve, PredefinedGamma},
+        raw_framebuf::RawFrameBuf,
+        testing::{RecordingDelay, RecordingInterface, Transfer},
+        Builder, ConfigurationError, Display, DisplayError, DisplayState, InitError, NoResetPin,
+    };
+
+    async fn recording_display() -> Display<RecordingInterface, ST7789, NoResetPin> {
+        let mut display = Builder::new(ST7789, RecordingInterface::new())
+            .init(&mut RecordingDelay::new())
+            .await
+            .unwrap();
+        display.di.clear();
+        display
+    }
+
+    #[test]
+    fn show_region_sends_rows() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            let mut buffer: Vec<u8> = (0..4 * 3 * 2).collect();
+            let fbuf = RawFrameBuf::<Rgb565, _>::new(&mut buffer[..], 4, 3);
+            display
+                .show_region(&fbuf, Rectangle::new(Point::new(1, 1), Size::new(2, 5)))
+                .await
+                .unwrap();
+
+            assert_eq!(
+                display.di.log(),
+                [
+                    Transfer::Command {
+                        command: 0x2A,
+                        args: vec![0, 1, 0, 2]
+                    },
+                    Transfer::Command {
+                        command: 0x2B,
+                        args: vec![0, 1, 0, 2]
+                    },
+                    Transfer::Command {
+                        command: 0x2C,
+                        args: vec![]
+                    },
+                    Transfer::Data(vec![10, 11, 12, 13]),
+                    Transfer::Data(vec![18, 19, 20, 21]),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn show_region_full_width_is_contiguous() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            let mut buffer: Vec<u8> = (0..4 * 3 * 2).collect();
+            let fbuf = RawFrameBuf::<Rgb565, _>::new(&mut buffer[..], 4, 3);
+            display
+                .show_region(&fbuf, Rectangle::new(Point::new(0, 1), Size::new(4, 2)))
+                .await
+                .unwrap();
+
+            assert_eq!(
+                display.di.log().last(),
+                Some(&Transfer::Data((8..24).collect()))
+            );
+        });
+    }
+
+    #[test]
+    fn show_region_packs_words() {
+        tokio_test::block_on(async {
+            let mut display = Builder::new(ST7789, RecordingInterface::<u16>::default())
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+            display.di.clear();
+
+            let mut buffer: Vec<u8> = (0..4 * 3 * 2).collect();
+            let fbuf = RawFrameBuf::<Rgb565, _>::new(&mut buffer[..], 4, 3);
+            display
+                .show_region(&fbuf, Rectangle::new(Point::new(1, 1), Size::new(2, 1)))
+                .await
+                .unwrap();
+
+            assert_eq!(
+                display.di.log().last(),
+                Some(&Transfer::Data(vec![0x0A0B, 0x0C0D]))
+            );
+        });
+    }
+
+    #[test]
+    fn show_dirty_sends_changed_area() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            let mut buffer = [0u8; 4 * 3 * 2];
+            let mut fbuf =
+                RawFrameBuf::<Rgb565, _>::new(&mut buffer[..], 4, 3).with_dirty_tracking();
+            Pixel(Point::new(2, 1), Rgb565::WHITE)
+                .draw(&mut fbuf)
+                .unwrap();
+
+            display.show_dirty(&mut fbuf).await.unwrap();
+            assert_eq!(
+                display.di.take_log()[..2],
+                [
+                    Transfer::Command {
+                        command: 0x2A,
+                        args: vec![0, 2, 0, 2]
+                    },
+                    Transfer::Command {
+                        command: 0x2B,
+                        args: vec![0, 1, 0, 1]
+                    },
+                ]
+            );
+
+            display.show_dirty(&mut fbuf).await.unwrap();
+            assert!(display.di.log().is_empty());
+        });
+    }
+
+    #[test]
+    fn show_region_outside_framebuffer() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            let mut buffer = [0u8; 4 * 3 * 2];
+            let fbuf = RawFrameBuf::<Rgb565, _>::new(&mut buffer[..], 4, 3);
+            display
+                .show_region(&fbuf, Rectangle::new(Point::new(10, 10), Size::new(4, 2)))
+                .await
+                .unwrap();
+
+            assert!(display.di.log().is_empty());
+        });
+    }
+
+    fn sent_data<W: Copy>(log: &[Transfer<W>]) -> Vec<W> {
+        log.iter()
+            .filter_map(|transfer| match transfer {
+                Transfer::Data(data) => Some(data.as_slice()),
+                _ => None,
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn fill_rect_repeats_pixel_pattern() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            display
+                .fill_rect(
+                    Rectangle::new(Point::new(1, 2), Size::new(10, 10)),
+                    Rgb565::RED,
+                )
+                .await
+                .unwrap();
+
+            let log = display.di.take_log();
+            assert_eq!(
+                log[..3],
+                [
+                    Transfer::Command {
+                        command: 0x2A,
+                        args: vec![0, 1, 0, 10]
+                    },
+                    Transfer::Command {
+                        command: 0x2B,
+                        args: vec![0, 2, 0, 11]
+                    },
+                    Transfer::Command {
+                        command: 0x2C,
+                        args: vec![]
+                    },
+                ]
+            );
+            assert_eq!(sent_data(&log), [0xF8, 0x00].repeat(100));
+        });
+    }
+
+    #[test]
+    fn fill_rect_is_clipped() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            display
+                .fill_rect(
+                    Rectangle::new(Point::new(-5, 310), Size::new(10, 20)),
+                    Rgb565::WHITE,
+                )
+                .await
+                .unwrap();
+
+            let log = display.di.take_log();
+            assert_eq!(
+                log[..2],
+                [
+                    Transfer::Command {
+                        command: 0x2A,
+                        args: vec![0, 0, 0, 4]
+                    },
+                    Transfer::Command {
+                        command: 0x2B,
+                        args: vec![0x01, 0x36, 0x01, 0x3F]
+                    },
+                ]
+            );
+            assert_eq!(sent_data(&log), [0xFF; 5 * 10 * 2]);
+
+            display
+                .fill_rect(
+                    Rectangle::new(Point::new(240, 0), Size::new(10, 10)),
+                    Rgb565::WHITE,
+                )
+                .await
+                .unwrap();
+            assert!(display.di.log().is_empty());
+        });
+    }
+
+    #[test]
+    fn clear_sends_whole_display() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            display.clear(Rgb565::BLACK).await.unwrap();
+
+            let log = display.di.take_log();
+            let data = sent_data(&log);
+            assert_eq!(data.len(), 240 * 320 * 2);
+            assert!(data.iter().all(|&byte| byte == 0));
+
+            // the color is sent in chunks of 512 bytes
+            let transfers = log
+                .iter()
+                .filter(|transfer| matches!(transfer, Transfer::Data(_)))
+                .count();
+            assert_eq!(transfers, 240 * 320 * 2 / 512);
+        });
+    }
+
+    #[test]
+    fn fill_rect_single_word_per_pixel() {
+        tokio_test::block_on(async {
+            let mut display = Builder::new(ST7789, RecordingInterface::<u16>::default())
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+            display.di.clear();
+
+            display
+                .fill_rect(
+                    Rectangle::new(Point::zero(), Size::new(20, 10)),
+                    Rgb565::RED,
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(sent_data(&display.di.take_log()), [0xF800; 200]);
+        });
+    }
+
+    #[test]
+    fn set_brightness_and_cabc_mode() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            display.set_brightness(0x80).await.unwrap();
+            display.set_cabc_mode(CabcMode::StillPicture).await.unwrap();
+
+            assert_eq!(
+                display.di.log(),
+                [
+                    Transfer::Command {
+                        command: 0x53,
+                        args: vec![0x24]
+                    },
+                    Transfer::Command {
+                        command: 0x51,
+                        args: vec![0x80]
+                    },
+                    Transfer::Command {
+                        command: 0x55,
+                        args: vec![0x02]
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn set_brightness_model_override() {
+        tokio_test::block_on(async {
+            let mut display = Builder::new(RM67162, RecordingInterface::new())
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+            display.di.clear();
+
+            display.set_brightness(0x10).await.unwrap();
+
+            assert_eq!(
+                display.di.log(),
+                [
+                    Transfer::Command {
+                        command: 0xFE,
+                        args: vec![0x00]
+                    },
+                    Transfer::Command {
+                        command: 0x51,
+                        args: vec![0x10]
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn builder_gamma() {
+        tokio_test::block_on(async {
+            let positive = &[1; 14];
+            let negative = &[2; 14];
+            let display = Builder::new(ST7789, RecordingInterface::new())
+                .gamma(GammaCurve::Custom { positive, negative })
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+
+            let log = display.di.log();
+            assert_eq!(
+                log[log.len() - 2..],
+                [
+                    Transfer::Command {
+                        command: 0xE0,
+                        args: vec![1; 14]
+                    },
+                    Transfer::Command {
+                        command: 0xE1,
+                        args: vec![2; 14]
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn set_gamma() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            let gamma = GammaCurve::Predefined(PredefinedGamma::Curve2);
+            display.set_gamma(gamma).await.unwrap();
+            display.set_gamma(GammaCurve::Default).await.unwrap();
+
+            assert_eq!(
+                display.di.log(),
+                [Transfer::Command {
+                    command: 0x26,
+                    args: vec![0x02]
+                }]
+            );
+        });
+    }
+
+    #[test]
+    fn unsupported_gamma() {
+        tokio_test::block_on(async {
+            let result = Builder::new(ST7789, RecordingInterface::new())
+                .gamma(GammaCurve::Custom {
+                    positive: &[1, 2, 3],
+                    negative: &[4, 5, 6],
+                })
+                .init(&mut RecordingDelay::new())
+                .await;
+            assert!(matches!(
+                result,
+                Err(InitError::InvalidConfiguration(
+                    ConfigurationError::UnsupportedGamma
+                ))
+            ));
+
+            let mut display = Builder::new(RM67162, RecordingInterface::new())
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+            display.di.clear();
+            let gamma = GammaCurve::Custom {
+                positive: &[0; 14],
+                negative: &[0; 14],
+            };
+            assert!(matches!(
+                display.set_gamma(gamma).await,
+                Err(DisplayError::InvalidConfiguration(
+                    ConfigurationError::UnsupportedGamma
+                ))
+            ));
+            assert!(display.di.log().is_empty());
+        });
+    }
+
+    #[test]
+    fn set_gamma_split_tables() {
+        tokio_test::block_on(async {
+            let mut display = Builder::new(GC9A01, RecordingInterface::new())
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+            display.di.clear();
+
+            let positive = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+            let negative = &[21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32];
+            display
+                .set_gamma(GammaCurve::Custom { positive, negative })
+                .await
+                .unwrap();
+
+            let short = GammaCurve::Custom {
+                positive: &[0; 6],
+                negative: &[0; 6],
+            };
+            assert!(display.set_gamma(short).await.is_err());
+
+            let commands: Vec<_> = display
+                .di
+                .log()
+                .iter()
+                .map(|transfer| match transfer {
+                    Transfer::Command { command, args } => (*command, args.clone()),
+                    _ => panic!("unexpected transfer"),
+                })
+                .collect();
+            assert_eq!(
+                commands,
+                [
+                    (0xF0, vec![1, 2, 3, 4, 5, 6]),
+                    (0xF1, vec![7, 8, 9, 10, 11, 12]),
+                    (0xF2, vec![21, 22, 23, 24, 25, 26]),
+                    (0xF3, vec![27, 28, 29, 30, 31, 32]),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn set_frame_rate() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            display
+                .set_frame_rate(FrameRate::new(0x1F).with_porch(0x10, 0x20))
+                .await
+                .unwrap();
+
+            assert_eq!(
+                display.di.log(),
+                [
+                    Transfer::Command {
+                        command: 0xB2,
+                        args: vec![0x20, 0x10, 0x00, 0x33, 0x33]
+                    },
+                    Transfer::Command {
+                        command: 0xC6,
+                        args: vec![0x1F]
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn set_frame_rate_unsupported() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+            assert!(matches!(
+                display.set_frame_rate(FrameRate::new(32)).await,
+                Err(DisplayError::InvalidConfiguration(
+                    ConfigurationError::UnsupportedFrameRate
+                ))
+            ));
+            assert!(display.di.log().is_empty());
+
+            let mut display = Builder::new(RM67162, RecordingInterface::new())
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+            assert!(matches!(
+                display.set_frame_rate(FrameRate::new(0)).await,
+                Err(DisplayError::InvalidConfiguration(
+                    ConfigurationError::UnsupportedFrameRate
+                ))
+            ));
+        });
+    }
+
+    #[test]
+    fn builder_frame_rate() {
+        tokio_test::block_on(async {
+            let display = Builder::new(ILI9341Rgb565, RecordingInterface::new())
+                .frame_rate(FrameRate::new(0x10).with_porch(4, 4))
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+
+            let log = display.di.log();
+            assert_eq!(
+                log[log.len() - 2..],
+                [
+                    Transfer::Command {
+                        command: 0xB1,
+                        args: vec![0x00, 0x10]
+                    },
+                    Transfer::Command {
+                        command: 0xB5,
+                        args: vec![4, 4, 0x0A, 0x14]
+                    },
+                ]
+            );
+
+            let mut di = RecordingInterface::new();
+            let mut delay = RecordingDelay::new();
+            let result = Builder::new(ILI9341Rgb565, &mut di)
+                .frame_rate(FrameRate::new(0x0F))
+                .init(&mut delay)
+                .await;
+            assert!(matches!(
+                result,
+                Err(InitError::InvalidConfiguration(
+                    ConfigurationError::UnsupportedFrameRate
+                ))
+            ));
+            // the settings are checked before the display is reset
+            assert!(di.log().is_empty());
+            assert!(delay.delays().is_empty());
+        });
+    }
+
+    #[derive(Default)]
+    struct TestBacklight(Vec<u8>);
+
+    impl Backlight for TestBacklight {
+        type Error = core::convert::Infallible;
+
+        fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+            self.0.push(level);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn backlight_follows_init_and_sleep() {
+        tokio_test::block_on(async {
+            let mut delay = RecordingDelay::new();
+            let display = Builder::new(ST7789, RecordingInterface::new())
+                .backlight(TestBacklight::default())
+                .backlight_level(200)
+                .init(&mut delay)
+                .await
+                .unwrap();
+            assert_eq!(display.backlight.0, [0, 200]);
+
+            let sleeping = display.sleep(&mut delay).await.unwrap();
+            assert_eq!(sleeping.display.backlight.0, [0, 200, 0]);
+
+            let mut display = sleeping.wake(&mut delay).await.unwrap();
+            display.set_backlight(50).unwrap();
+            assert_eq!(display.backlight(), 50);
+
+            let (display, backlight) = display.release_backlight();
+            assert_eq!(backlight.0, [0, 200, 0, 200, 50]);
+            let _ = display.release();
+        });
+    }
+
+    /// Backlight which fails to turn off once `fail` is set.
+    struct FailingBacklight {
+        fail: bool,
+    }
+
+    impl Backlight for FailingBacklight {
+        type Error = ();
+
+        fn set_level(&mut self, level: u8) -> Result<(), Self::Error> {
+            if self.fail && level == 0 {
+                Err(())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn sleep_error_returns_display() {
+        tokio_test::block_on(async {
+            let mut delay = RecordingDelay::new();
+            let mut display = Builder::new(ST7789, RecordingInterface::new())
+                .backlight(FailingBacklight { fail: false })
+                .init(&mut delay)
+                .await
+                .unwrap();
+            display.di.clear();
+            display.backlight.fail = true;
+
+            let Err(error) = display.sleep(&mut delay).await else {
+                panic!("sleep should fail");
+            };
+            assert!(matches!(error.error, DisplayError::Backlight(())));
+            let mut display = error.display;
+            display.backlight.fail = false;
+
+            let Ok(sleeping) = display.sleep(&mut delay).await else {
+                panic!("sleep should succeed");
+            };
+            let (display, _) = sleeping.into_inner().release_backlight();
+            let (di, _, _) = display.release();
+            assert_eq!(
+                di.log(),
+                [Transfer::Command {
+                    command: 0x10,
+                    args: vec![]
+                }]
+            );
+        });
+    }
+
+    #[test]
+    fn display_state() {
+        tokio_test::block_on(async {
+            let mut delay = RecordingDelay::new();
+            let mut state = DisplayState::from(recording_display().await);
+            assert!(!state.is_sleeping());
+
+            state = state.sleep(&mut delay).await.unwrap();
+            assert!(state.is_sleeping());
+            assert!(state.awake_mut().is_none());
+            state = state.sleep(&mut delay).await.unwrap();
+
+            state = state.wake(&mut delay).await.unwrap();
+            let display = state.awake_mut().unwrap();
+            assert_eq!(
+                display.di.log(),
+                [
+                    Transfer::Command {
+                        command: 0x10,
+                        args: vec![]
+                    },
+                    Transfer::Command {
+                        command: 0x11,
+                        args: vec![]
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn partial_mode() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            display.enter_partial_mode(16..320).await.unwrap();
+            display.exit_partial_mode().await.unwrap();
+
+            assert_eq!(
+                display.di.log(),
+                [
+                    Transfer::Command {
+                        command: 0x30,
+                        args: vec![0, 0x10, 0x01, 0x3F]
+                    },
+                    Transfer::Command {
+                        command: 0x12,
+                        args: vec![]
+                    },
+                    Transfer::Command {
+                        command: 0x13,
+                        args: vec![]
+                    },
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn partial_mode_invalid_area() {
+        tokio_test::block_on(async {
+            let mut display = recording_display().await;
+
+            for rows in [10..10, 300..321] {
+                assert!(matches!(
+                    display.enter_partial_mode(rows).await,
+                    Err(DisplayError::InvalidConfiguration(
+                        ConfigurationError::InvalidPartialArea
+                    ))
+                ));
+            }
+            assert!(display.di.log().is_empty());
+        });
+    }
 }
@@ -9,6 +9,9 @@ use crate::{
 use embedded_graphics_core::prelude::RgbColor;
 use embedded_hal_async::delay::DelayNs;
 
+mod any_model;
+pub use any_model::*;
+
 // existing model implementations
 mod gc9107;
 mod gc9a01;
@@ -47,6 +50,11 @@ pub trait Model {
     /// Duration of the active low reset pulse in µs.
     const RESET_DURATION: u32 = 10;
 
+    /// Length of the custom gamma correction tables in bytes.
+    ///
+    /// `None` if the model doesn't support [custom gamma tables](options::GammaCurve::Custom).
+    const GAMMA_TABLE_LEN: Option<usize> = None;
+
     /// Initializes the display for this model with MADCTL from [crate::Display]
     /// and returns the value of MADCTL set by init
     fn init<DELAY, DI>(
@@ -167,6 +175,100 @@ pub trait Model {
         }
     }
 
+    /// Sets the display brightness.
+    ///
+    /// The default implementation enables the brightness control and backlight outputs with
+    /// [`WriteCtrlDisplay`](dcs::WriteCtrlDisplay) and sets the brightness with
+    /// [`WriteDisplayBrightness`](dcs::WriteDisplayBrightness).
+    fn set_brightness<DI>(
+        di: &mut DI,
+        brightness: u8,
+    ) -> impl core::future::Future<Output = Result<(), DI::Error>>
+    where
+        DI: Interface,
+    {
+        async move {
+            di.write_command(dcs::WriteCtrlDisplay::new(true, false, true))
+                .await?;
+            di.write_command(dcs::WriteDisplayBrightness::new(brightness))
+                .await
+        }
+    }
+
+    /// Sets the content adaptive brightness control mode.
+    fn set_cabc_mode<DI>(
+        di: &mut DI,
+        mode: options::CabcMode,
+    ) -> impl core::future::Future<Output = Result<(), DI::Error>>
+    where
+        DI: Interface,
+    {
+        async move { di.write_command(dcs::WriteCabcMode::new(mode)).await }
+    }
+
+    /// Checks if the frame rate settings are supported by the model.
+    ///
+    /// The default implementation returns [`ConfigurationError::UnsupportedFrameRate`], models
+    /// with frame rate control override this method and check the valid ranges. See
+    /// [`FrameRate`](options::FrameRate) for the supported models.
+    fn validate_frame_rate(_frame_rate: options::FrameRate) -> Result<(), ConfigurationError> {
+        Err(ConfigurationError::UnsupportedFrameRate)
+    }
+
+    /// Sets the frame rate and porches.
+    ///
+    /// This is only called with settings which were accepted by
+    /// [`validate_frame_rate`](Self::validate_frame_rate). The default implementation does
+    /// nothing.
+    fn set_frame_rate<DI>(
+        _di: &mut DI,
+        _frame_rate: options::FrameRate,
+    ) -> impl core::future::Future<Output = Result<(), DI::Error>>
+    where
+        DI: Interface,
+    {
+        async move { Ok(()) }
+    }
+
+    /// Checks if the gamma curve is supported by the model.
+    ///
+    /// The default implementation accepts predefined curves and custom tables with a length
+    /// of [`GAMMA_TABLE_LEN`](Self::GAMMA_TABLE_LEN) bytes. Returns
+    /// [`ConfigurationError::UnsupportedGamma`] otherwise.
+    fn validate_gamma(gamma: options::GammaCurve) -> Result<(), ConfigurationError> {
+        match gamma {
+            options::GammaCurve::Default | options::GammaCurve::Predefined(_) => Ok(()),
+            options::GammaCurve::Custom { positive, negative } => match Self::GAMMA_TABLE_LEN {
+                Some(len) if positive.len() == len && negative.len() == len => Ok(()),
+                _ => Err(ConfigurationError::UnsupportedGamma),
+            },
+        }
+    }
+
+    /// Sets the gamma curve.
+    ///
+    /// This is only called with gamma curves which were accepted by
+    /// [`validate_gamma`](Self::validate_gamma). The default implementation selects
+    /// [predefined](options::GammaCurve::Predefined) curves with the DCS Gamma Set command.
+    /// Models with gamma correction registers override this method to write custom tables.
+    /// [`GammaCurve::Default`](options::GammaCurve::Default) doesn't change the gamma curve.
+    fn set_gamma<DI>(
+        di: &mut DI,
+        gamma: options::GammaCurve,
+    ) -> impl core::future::Future<Output = Result<(), DI::Error>>
+    where
+        DI: Interface,
+    {
+        async move {
+            match gamma {
+                options::GammaCurve::Predefined(curve) => {
+                    di.write_command(dcs::SetGammaCurve::new(curve)).await
+                }
+                options::GammaCurve::Default | options::GammaCurve::Custom { .. } => Ok(()),
+            }
+        }
+    }
+
     /// Sets the vertical scroll region.
     ///
     /// The `top_fixed_area` and `bottom_fixed_area` arguments can be used to
@@ -228,6 +330,31 @@ pub trait Model {
     }
 }
 
+/// Sets the gamma curve using a pair of positive and negative gamma correction registers.
+///
+/// Used by the [`Model::set_gamma`] implementations of the ST77xx and ILI93xx/ILI948x
+/// families (`0xE0` and `0xE1`) and the GC9107 (`0xF0` and `0xF1`).
+pub(crate) async fn set_gamma_tables<DI>(
+    di: &mut DI,
+    gamma: options::GammaCurve,
+    positive_register: u8,
+    negative_register: u8,
+) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    match gamma {
+        options::GammaCurve::Default => Ok(()),
+        options::GammaCurve::Predefined(curve) => {
+            di.write_command(dcs::SetGammaCurve::new(curve)).await
+        }
+        options::GammaCurve::Custom { positive, negative } => {
+            di.write_raw(positive_register, positive).await?;
+            di.write_raw(negative_register, negative).await
+        }
+    }
+}
+
 /// Error returned by [`Model::init`].
 ///
 /// This error type is used internally by implementations of the [`Model`]
@@ -0,0 +1,128 @@
+use crate::{
+    dcs::{DisplayId, ReadDisplayId, ReadInterfaceExt},
+    interface::{DummyCycles, ReadInterface},
+    models::{
+        ILI9341Rgb565, ILI9342CRgb565, ILI9486Rgb565, ILI9488Rgb565, Model, ST7735s, GC9107,
+        GC9A01, ST7789, ST7796,
+    },
+};
+
+/// Manufacturer specific "Read ID4" command used by ILI93xx, ILI948x and ST7796 controllers.
+const READ_ID4: u8 = 0xD3;
+
+/// Controller model which can be detected at runtime.
+///
+/// Use [`Builder::init_detect`](crate::Builder::init_detect) to initialize an
+/// [`AnyDisplay`](crate::AnyDisplay) for the controller which is connected to the interface.
+/// All variants use the `Rgb565` color format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum AnyModel {
+    /// [`GC9107`]
+    GC9107,
+    /// [`GC9A01`]
+    GC9A01,
+    /// [`ILI9341Rgb565`]
+    ILI9341Rgb565,
+    /// [`ILI9342CRgb565`]
+    ILI9342CRgb565,
+    /// [`ILI9486Rgb565`]
+    ILI9486Rgb565,
+    /// [`ILI9488Rgb565`]
+    ILI9488Rgb565,
+    /// [`ST7735s`]
+    ST7735s,
+    /// [`ST7789`]
+    ST7789,
+    /// [`ST7796`]
+    ST7796,
+}
+
+impl AnyModel {
+    /// Reads the controller ID and returns the matching model.
+    ///
+    /// The MIPI DCS Read Display ID (0x04) command is tried first, which is supported by
+    /// the Sitronix and GalaxyCore controllers. ILI93xx, ILI948x and ST7796 controllers are
+    /// identified by the manufacturer specific Read ID4 (0xD3) command.
+    ///
+    /// Returns `None` if the ID doesn't match any known controller. The display must be
+    /// reset and ready to accept commands before this method is called.
+    pub async fn detect<DI>(di: &mut DI) -> Result<Option<Self>, DI::Error>
+    where
+        DI: ReadInterface,
+    {
+        let id = di.read_command_response(ReadDisplayId).await?;
+        if let Some(model) = Self::from_display_id(id) {
+            return Ok(Some(model));
+        }
+
+        let mut id4 = [0u8; 3];
+        di.read_raw(READ_ID4, DummyCycles::Byte, &mut id4).await?;
+        Ok(Self::from_id4(id4))
+    }
+
+    /// Returns the model matching the response of the Read Display ID (0x04) command.
+    pub fn from_display_id(id: DisplayId) -> Option<Self> {
+        match (id.manufacturer, id.version, id.driver) {
+            (0x00, 0x91, 0x07) => Some(Self::GC9107),
+            (0x00, 0x9A, 0x01) => Some(Self::GC9A01),
+            (0x7C, 0x89, 0xF0) => Some(Self::ST7735s),
+            (0x85, 0x85, 0x52) => Some(Self::ST7789),
+            _ => None,
+        }
+    }
+
+    /// Returns the model matching the response of the Read ID4 (0xD3) command.
+    ///
+    /// `id4` contains the three parameter bytes following the dummy parameter.
+    pub fn from_id4(id4: [u8; 3]) -> Option<Self> {
+        match (id4[1], id4[2]) {
+            (0x93, 0x41) => Some(Self::ILI9341Rgb565),
+            (0x93, 0x42) => Some(Self::ILI9342CRgb565),
+            (0x94, 0x86) => Some(Self::ILI9486Rgb565),
+            (0x94, 0x88) => Some(Self::ILI9488Rgb565),
+            (0x77, 0x96) => Some(Self::ST7796),
+            _ => None,
+        }
+    }
+
+    /// Returns the framebuffer size of the model.
+    pub const fn framebuffer_size(&self) -> (u16, u16) {
+        match self {
+            Self::GC9107 => GC9107::FRAMEBUFFER_SIZE,
+            Self::GC9A01 => GC9A01::FRAMEBUFFER_SIZE,
+            Self::ILI9341Rgb565 => ILI9341Rgb565::FRAMEBUFFER_SIZE,
+            Self::ILI9342CRgb565 => ILI9342CRgb565::FRAMEBUFFER_SIZE,
+            Self::ILI9486Rgb565 => ILI9486Rgb565::FRAMEBUFFER_SIZE,
+            Self::ILI9488Rgb565 => ILI9488Rgb565::FRAMEBUFFER_SIZE,
+            Self::ST7735s => ST7735s::FRAMEBUFFER_SIZE,
+            Self::ST7789 => ST7789::FRAMEBUFFER_SIZE,
+            Self::ST7796 => ST7796::FRAMEBUFFER_SIZE,
+        }
+    }
+}
+
+macro_rules! any_model_from {
+    ($($model:ident),*) => {
+        $(
+            impl From<$model> for AnyModel {
+                fn from(_: $model) -> Self {
+                    Self::$model
+                }
+            }
+        )*
+    };
+}
+
+any_model_from!(
+    GC9107,
+    GC9A01,
+    ILI9341Rgb565,
+    ILI9342CRgb565,
+    ILI9486Rgb565,
+    ILI9488Rgb565,
+    ST7735s,
+    ST7789,
+    ST7796
+);
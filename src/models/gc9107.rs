@@ -7,8 +7,8 @@ use crate::{
         SetInvertMode, SetPixelFormat,
     },
     interface::{Interface, InterfaceKind},
-    models::{Model, ModelInitError},
-    options::ModelOptions,
+    models::{set_gamma_tables, Model, ModelInitError},
+    options::{GammaCurve, ModelOptions},
     ConfigurationError,
 };
 
@@ -18,6 +18,7 @@ pub struct GC9107;
 impl Model for GC9107 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (128, 160);
+    const GAMMA_TABLE_LEN: Option<usize> = Some(14);
 
     async fn init<DELAY, DI>(
         &mut self,
@@ -31,7 +32,7 @@ impl Model for GC9107 {
     {
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit
+            InterfaceKind::Serial4Line | InterfaceKind::Serial3Line | InterfaceKind::Parallel8Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -101,4 +102,11 @@ impl Model for GC9107 {
 
         Ok(madctl)
     }
+
+    async fn set_gamma<DI>(di: &mut DI, gamma: GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_gamma_tables(di, gamma, 0xF0, 0xF1).await
+    }
 }
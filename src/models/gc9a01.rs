@@ -4,11 +4,11 @@ use embedded_hal_async::delay::DelayNs;
 use crate::{
     dcs::{
         BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        SetGammaCurve, SetInvertMode, SetPixelFormat,
     },
     interface::{Interface, InterfaceKind},
     models::{Model, ModelInitError},
-    options::ModelOptions,
+    options::{FrameRate, GammaCurve, ModelOptions},
     ConfigurationError,
 };
 
@@ -18,6 +18,7 @@ pub struct GC9A01;
 impl Model for GC9A01 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 240);
+    const GAMMA_TABLE_LEN: Option<usize> = Some(12);
 
     async fn init<DELAY, DI>(
         &mut self,
@@ -31,7 +32,10 @@ impl Model for GC9A01 {
     {
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+            InterfaceKind::Serial4Line
+                | InterfaceKind::Serial3Line
+                | InterfaceKind::Parallel8Bit
+                | InterfaceKind::Parallel16Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -143,4 +147,41 @@ impl Model for GC9A01 {
 
         Ok(madctl)
     }
+
+    async fn set_gamma<DI>(di: &mut DI, gamma: GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        match gamma {
+            // Each table is split across two registers, e.g. the positive table is written
+            // to SET_GAMMA1 (0xF0) and SET_GAMMA2 (0xF1).
+            GammaCurve::Custom { positive, negative } => {
+                let (positive1, positive2) = positive.split_at(6);
+                let (negative1, negative2) = negative.split_at(6);
+                di.write_raw(0xF0, positive1).await?;
+                di.write_raw(0xF1, positive2).await?;
+                di.write_raw(0xF2, negative1).await?;
+                di.write_raw(0xF3, negative2).await
+            }
+            GammaCurve::Predefined(curve) => di.write_command(SetGammaCurve::new(curve)).await,
+            GammaCurve::Default => Ok(()),
+        }
+    }
+
+    fn validate_frame_rate(frame_rate: FrameRate) -> Result<(), ConfigurationError> {
+        if frame_rate.line_period > 15 || frame_rate.porch.is_some() {
+            return Err(ConfigurationError::UnsupportedFrameRate);
+        }
+
+        Ok(())
+    }
+
+    async fn set_frame_rate<DI>(di: &mut DI, frame_rate: FrameRate) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Frame Rate (0xE8): bits 6-4 select the inversion mode and are kept at 0b011 as set by
+        // the init sequence, bits 3-0 set the line period (RTN).
+        di.write_raw(0xE8, &[0x30 | frame_rate.line_period]).await
+    }
 }
@@ -7,6 +7,7 @@ use crate::{
     interface::Interface,
     models::{Model, ModelInitError},
     options::ModelOptions,
+    ConfigurationError,
 };
 use embedded_graphics_core::pixelcolor::Rgb565;
 use embedded_hal_async::delay::DelayNs;
@@ -274,6 +275,20 @@ impl Model for ILI9225Rgb565 {
 
         di.write_raw(ILI9225_DISP_CTRL1, &[high, low]).await
     }
+    async fn set_brightness<DI>(_di: &mut DI, _brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
+    async fn set_cabc_mode<DI>(_di: &mut DI, _mode: options::CabcMode) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
     async fn set_vertical_scroll_region<DI>(
         _di: &mut DI,
         _top_fixed_area: u16,
@@ -292,6 +307,20 @@ impl Model for ILI9225Rgb565 {
         // Not support, ignore it
         Ok(())
     }
+    fn validate_gamma(gamma: options::GammaCurve) -> Result<(), ConfigurationError> {
+        // Only the gamma curve set by the init sequence is supported
+        match gamma {
+            options::GammaCurve::Default => Ok(()),
+            _ => Err(ConfigurationError::UnsupportedGamma),
+        }
+    }
+    async fn set_gamma<DI>(_di: &mut DI, _gamma: options::GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // Not support, ignore it
+        Ok(())
+    }
 }
 
 crate::dcs::macros::dcs_basic_command!(
@@ -4,8 +4,8 @@ use embedded_hal_async::delay::DelayNs;
 use crate::{
     dcs::{BitsPerPixel, PixelFormat, SetAddressMode},
     interface::{Interface, InterfaceKind},
-    models::{ili934x, Model, ModelInitError},
-    options::ModelOptions,
+    models::{ili934x, set_gamma_tables, Model, ModelInitError},
+    options::{FrameRate, GammaCurve, ModelOptions},
     ConfigurationError,
 };
 
@@ -18,6 +18,7 @@ pub struct ILI9341Rgb666;
 impl Model for ILI9341Rgb565 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    const GAMMA_TABLE_LEN: Option<usize> = Some(15);
 
     async fn init<DELAY, DI>(
         &mut self,
@@ -31,7 +32,10 @@ impl Model for ILI9341Rgb565 {
     {
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+            InterfaceKind::Serial4Line
+                | InterfaceKind::Serial3Line
+                | InterfaceKind::Parallel8Bit
+                | InterfaceKind::Parallel16Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -43,11 +47,30 @@ impl Model for ILI9341Rgb565 {
             .await
             .map_err(Into::into)
     }
+
+    async fn set_gamma<DI>(di: &mut DI, gamma: GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_gamma_tables(di, gamma, 0xE0, 0xE1).await
+    }
+
+    fn validate_frame_rate(frame_rate: FrameRate) -> Result<(), ConfigurationError> {
+        ili934x::validate_frame_rate(frame_rate)
+    }
+
+    async fn set_frame_rate<DI>(di: &mut DI, frame_rate: FrameRate) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        ili934x::set_frame_rate(di, frame_rate).await
+    }
 }
 
 impl Model for ILI9341Rgb666 {
     type ColorFormat = Rgb666;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    const GAMMA_TABLE_LEN: Option<usize> = Some(15);
 
     async fn init<DELAY, DI>(
         &mut self,
@@ -61,7 +84,10 @@ impl Model for ILI9341Rgb666 {
     {
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+            InterfaceKind::Serial4Line
+                | InterfaceKind::Serial3Line
+                | InterfaceKind::Parallel8Bit
+                | InterfaceKind::Parallel16Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -73,4 +99,22 @@ impl Model for ILI9341Rgb666 {
             .await
             .map_err(Into::into)
     }
+
+    async fn set_gamma<DI>(di: &mut DI, gamma: GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_gamma_tables(di, gamma, 0xE0, 0xE1).await
+    }
+
+    fn validate_frame_rate(frame_rate: FrameRate) -> Result<(), ConfigurationError> {
+        ili934x::validate_frame_rate(frame_rate)
+    }
+
+    async fn set_frame_rate<DI>(di: &mut DI, frame_rate: FrameRate) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        ili934x::set_frame_rate(di, frame_rate).await
+    }
 }
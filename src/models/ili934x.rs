@@ -6,7 +6,8 @@ use crate::{
         SetInvertMode, SetPixelFormat,
     },
     interface::Interface,
-    options::ModelOptions,
+    options::{FrameRate, ModelOptions},
+    ConfigurationError,
 };
 
 /// Common init for all ILI934x controllers and color formats.
@@ -49,3 +50,28 @@ where
 
     Ok(madctl)
 }
+
+/// Checks the frame rate and porch ranges of ILI934x controllers.
+pub fn validate_frame_rate(frame_rate: FrameRate) -> Result<(), ConfigurationError> {
+    let porch_valid = frame_rate.porch.map_or(true, |(front, back)| {
+        (2..=127).contains(&front) && (2..=127).contains(&back)
+    });
+    if !(16..=31).contains(&frame_rate.line_period) || !porch_valid {
+        return Err(ConfigurationError::UnsupportedFrameRate);
+    }
+
+    Ok(())
+}
+
+/// Sets the frame rate (FRMCTR1) and porches (Blanking Porch Control) for ILI934x controllers.
+pub async fn set_frame_rate<DI>(di: &mut DI, frame_rate: FrameRate) -> Result<(), DI::Error>
+where
+    DI: Interface,
+{
+    di.write_raw(0xB1, &[0x00, frame_rate.line_period]).await?; // FRMCTR1, division ratio 1
+    if let Some((front, back)) = frame_rate.porch {
+        di.write_raw(0xB5, &[front, back, 0x0A, 0x14]).await?; // default horizontal porches
+    }
+
+    Ok(())
+}
@@ -4,8 +4,8 @@ use embedded_hal_async::delay::DelayNs;
 use crate::{
     dcs::{BitsPerPixel, PixelFormat, SetAddressMode},
     interface::{Interface, InterfaceKind},
-    models::{ili948x, Model, ModelInitError},
-    options::ModelOptions,
+    models::{ili948x, set_gamma_tables, Model, ModelInitError},
+    options::{GammaCurve, ModelOptions},
     ConfigurationError,
 };
 
@@ -18,6 +18,7 @@ pub struct ILI9486Rgb666;
 impl Model for ILI9486Rgb565 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLE_LEN: Option<usize> = Some(15);
 
     async fn init<DELAY, DI>(
         &mut self,
@@ -43,11 +44,19 @@ impl Model for ILI9486Rgb565 {
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
         ili948x::init_common(di, delay, options, pf).await
     }
+
+    async fn set_gamma<DI>(di: &mut DI, gamma: GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_gamma_tables(di, gamma, 0xE0, 0xE1).await
+    }
 }
 
 impl Model for ILI9486Rgb666 {
     type ColorFormat = Rgb666;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLE_LEN: Option<usize> = Some(15);
 
     async fn init<DELAY, DI>(
         &mut self,
@@ -61,7 +70,12 @@ impl Model for ILI9486Rgb666 {
     {
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+            InterfaceKind::Serial4Line
+                | InterfaceKind::Serial3Line
+                | InterfaceKind::Parallel8Bit
+                | InterfaceKind::Parallel9Bit
+                | InterfaceKind::Parallel16Bit
+                | InterfaceKind::Parallel18Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -73,4 +87,11 @@ impl Model for ILI9486Rgb666 {
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
         ili948x::init_common(di, delay, options, pf).await
     }
+
+    async fn set_gamma<DI>(di: &mut DI, gamma: GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_gamma_tables(di, gamma, 0xE0, 0xE1).await
+    }
 }
@@ -30,9 +30,7 @@ where
     di.write_command(SetInvertMode::new(options.invert_colors))
         .await?;
 
-    // optional gamma setup
-    // dcs.write_raw(Instruction::PGC, &[0x00, 0x2C, 0x2C, 0x0B, 0x0C, 0x04, 0x4C, 0x64, 0x36, 0x03, 0x0E, 0x01, 0x10, 0x01, 0x00])?; // Positive Gamma Control
-    // dcs.write_raw(Instruction::NGC, &[0x0F, 0x37, 0x37, 0x0C, 0x0F, 0x05, 0x50, 0x32, 0x36, 0x04, 0x0B, 0x00, 0x19, 0x14, 0x0F])?; // Negative Gamma Control
+    // optional gamma setup: use `GammaCurve::Custom` to write PGC (0xE0) and NGC (0xE1)
 
     di.write_raw(0xB6, &[0b0000_0010, 0x02]).await?; // DFC - dropped third parameter
     di.write_command(EnterNormalMode).await?; // turn to normal mode
@@ -4,7 +4,7 @@ use embedded_hal_async::delay::DelayNs;
 use crate::{
     dcs::{
         BitsPerPixel, ExitSleepMode, InterfaceExt, PixelFormat, SetAddressMode, SetDisplayOn,
-        SetInvertMode, SetPixelFormat,
+        SetInvertMode, SetPixelFormat, WriteCtrlDisplay, WriteDisplayBrightness,
     },
     interface::{Interface, InterfaceKind},
     models::{Model, ModelInitError},
@@ -19,6 +19,8 @@ use crate::{
 /// - 240x536 resolution
 ///
 /// This driver was developed for the Lilygo T-Display-S3 AMOLED display (v2).
+/// The display can be connected over SPI or, for higher frame rates, over QSPI using
+/// [`QspiInterface`](crate::interface::QspiInterface).
 /// The initialization sequence is based on Lilygo's Arduino example code.
 ///
 /// Currently only tested with 240x536 resolution displays.
@@ -43,7 +45,10 @@ impl Model for RM67162 {
     {
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit
+            InterfaceKind::Serial4Line
+                | InterfaceKind::Serial3Line
+                | InterfaceKind::Qspi
+                | InterfaceKind::Parallel8Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -63,8 +68,9 @@ impl Model for RM67162 {
         di.write_raw(0xFE, &[0x0A]).await?;
         di.write_raw(0x29, &[0x10]).await?;
         di.write_raw(0xFE, &[0x00]).await?;
-        di.write_raw(0x51, &[0xaf]).await?; // Set brightness
-        di.write_raw(0x53, &[0x20]).await?;
+        di.write_command(WriteDisplayBrightness::new(0xAF)).await?;
+        di.write_command(WriteCtrlDisplay::new(true, false, false))
+            .await?;
         di.write_raw(0x35, &[0x00]).await?;
 
         let pf = PixelFormat::with_all(BitsPerPixel::from_rgb_color::<Self::ColorFormat>());
@@ -84,4 +90,15 @@ impl Model for RM67162 {
 
         Ok(madctl)
     }
+
+    async fn set_brightness<DI>(di: &mut DI, brightness: u8) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        // The brightness is only writable on the user command page. There is no backlight,
+        // brightness control was already enabled by `init`.
+        di.write_raw(0xFE, &[0x00]).await?;
+        di.write_command(WriteDisplayBrightness::new(brightness))
+            .await
+    }
 }
@@ -7,17 +7,21 @@ use crate::{
         SetInvertMode, SetPixelFormat,
     },
     interface::{Interface, InterfaceKind},
-    models::{Model, ModelInitError},
-    options::ModelOptions,
+    models::{set_gamma_tables, Model, ModelInitError},
+    options::{FrameRate, GammaCurve, ModelOptions},
     ConfigurationError,
 };
 
 /// ST7735s display in Rgb565 color mode.
 pub struct ST7735s;
 
+/// Front and back porch set by the init sequence.
+const DEFAULT_PORCH: (u8, u8) = (0x3A, 0x3A);
+
 impl Model for ST7735s {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (132, 162);
+    const GAMMA_TABLE_LEN: Option<usize> = Some(16);
 
     async fn init<DELAY, DI>(
         &mut self,
@@ -31,7 +35,10 @@ impl Model for ST7735s {
     {
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+            InterfaceKind::Serial4Line
+                | InterfaceKind::Serial3Line
+                | InterfaceKind::Parallel8Bit
+                | InterfaceKind::Parallel16Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -83,4 +90,33 @@ impl Model for ST7735s {
 
         Ok(madctl)
     }
+
+    async fn set_gamma<DI>(di: &mut DI, gamma: GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_gamma_tables(di, gamma, 0xE0, 0xE1).await
+    }
+
+    fn validate_frame_rate(frame_rate: FrameRate) -> Result<(), ConfigurationError> {
+        let (front, back) = frame_rate.porch.unwrap_or(DEFAULT_PORCH);
+        if frame_rate.line_period > 15 || !(1..=63).contains(&front) || !(1..=63).contains(&back) {
+            return Err(ConfigurationError::UnsupportedFrameRate);
+        }
+
+        Ok(())
+    }
+
+    async fn set_frame_rate<DI>(di: &mut DI, frame_rate: FrameRate) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        let (front, back) = frame_rate.porch.unwrap_or(DEFAULT_PORCH);
+        let rtna = frame_rate.line_period;
+        di.write_raw(0xB1, &[rtna, front, back]).await?; // set frame rate in normal mode
+        di.write_raw(0xB2, &[rtna, front, back]).await?; // set frame rate in idle mode
+        di.write_raw(0xB3, &[rtna, front, back, rtna, front, back])
+            .await?; // set frame rate in partial mode
+        Ok(())
+    }
 }
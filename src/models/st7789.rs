@@ -7,8 +7,8 @@ use crate::{
         SetDisplayOn, SetInvertMode, SetPixelFormat,
     },
     interface::{Interface, InterfaceKind},
-    models::{Model, ModelInitError},
-    options::ModelOptions,
+    models::{set_gamma_tables, Model, ModelInitError},
+    options::{FrameRate, GammaCurve, ModelOptions},
     ConfigurationError,
 };
 
@@ -18,6 +18,7 @@ pub struct ST7789;
 impl Model for ST7789 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (240, 320);
+    const GAMMA_TABLE_LEN: Option<usize> = Some(14);
 
     async fn init<DELAY, DI>(
         &mut self,
@@ -31,7 +32,10 @@ impl Model for ST7789 {
     {
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+            InterfaceKind::Serial4Line
+                | InterfaceKind::Serial3Line
+                | InterfaceKind::Parallel8Bit
+                | InterfaceKind::Parallel16Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -63,4 +67,34 @@ impl Model for ST7789 {
 
         Ok(madctl)
     }
+
+    async fn set_gamma<DI>(di: &mut DI, gamma: GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_gamma_tables(di, gamma, 0xE0, 0xE1).await
+    }
+
+    fn validate_frame_rate(frame_rate: FrameRate) -> Result<(), ConfigurationError> {
+        let porch_valid = frame_rate.porch.map_or(true, |(front, back)| {
+            (1..=127).contains(&front) && (1..=127).contains(&back)
+        });
+        if frame_rate.line_period > 31 || !porch_valid {
+            return Err(ConfigurationError::UnsupportedFrameRate);
+        }
+
+        Ok(())
+    }
+
+    async fn set_frame_rate<DI>(di: &mut DI, frame_rate: FrameRate) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        if let Some((front, back)) = frame_rate.porch {
+            di.write_raw(0xB2, &[back, front, 0x00, 0x33, 0x33]).await?; // porch control
+        }
+        di.write_raw(0xC6, &[frame_rate.line_period]).await?; // frame rate control in normal mode
+
+        Ok(())
+    }
 }
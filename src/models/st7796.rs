@@ -4,8 +4,8 @@ use embedded_hal_async::delay::DelayNs;
 use crate::{
     dcs::SetAddressMode,
     interface::{Interface, InterfaceKind},
-    models::{Model, ModelInitError},
-    options::ModelOptions,
+    models::{set_gamma_tables, Model, ModelInitError},
+    options::{GammaCurve, ModelOptions},
     ConfigurationError,
 };
 
@@ -15,6 +15,7 @@ pub struct ST7796;
 impl Model for ST7796 {
     type ColorFormat = Rgb565;
     const FRAMEBUFFER_SIZE: (u16, u16) = (320, 480);
+    const GAMMA_TABLE_LEN: Option<usize> = Some(14);
 
     async fn init<DELAY, DI>(
         &mut self,
@@ -28,7 +29,10 @@ impl Model for ST7796 {
     {
         if !matches!(
             DI::KIND,
-            InterfaceKind::Serial4Line | InterfaceKind::Parallel8Bit | InterfaceKind::Parallel16Bit
+            InterfaceKind::Serial4Line
+                | InterfaceKind::Serial3Line
+                | InterfaceKind::Parallel8Bit
+                | InterfaceKind::Parallel16Bit
         ) {
             return Err(ModelInitError::InvalidConfiguration(
                 ConfigurationError::UnsupportedInterface,
@@ -37,4 +41,11 @@ impl Model for ST7796 {
 
         super::ST7789.init(di, delay, options).await
     }
+
+    async fn set_gamma<DI>(di: &mut DI, gamma: GammaCurve) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+    {
+        set_gamma_tables(di, gamma, 0xE0, 0xE1).await
+    }
 }
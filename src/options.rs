@@ -23,6 +23,10 @@ pub struct ModelOptions {
     pub display_size: (u16, u16),
     /// Display offset (x, y) for given display.
     pub display_offset: (u16, u16),
+    /// Gamma curve.
+    pub gamma: GammaCurve,
+    /// Frame rate, `None` keeps the frame rate set by the init sequence of the model.
+    pub frame_rate: Option<FrameRate>,
 }
 
 impl ModelOptions {
@@ -35,6 +39,8 @@ impl ModelOptions {
             refresh_order: RefreshOrder::default(),
             display_size: M::FRAMEBUFFER_SIZE,
             display_offset: (0, 0),
+            gamma: GammaCurve::default(),
+            frame_rate: None,
         }
     }
 
@@ -47,6 +53,8 @@ impl ModelOptions {
             refresh_order: RefreshOrder::default(),
             display_size,
             display_offset,
+            gamma: GammaCurve::default(),
+            frame_rate: None,
         }
     }
 
@@ -170,6 +178,113 @@ pub enum TearingEffect {
     HorizontalAndVertical,
 }
 
+/// Content adaptive brightness control (CABC) mode.
+///
+/// CABC reduces the backlight brightness for dark images to save power. The modes differ in
+/// how aggressively the brightness is reduced.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CabcMode {
+    /// CABC disabled.
+    #[default]
+    Off,
+    /// Mode for user interfaces, with a small reduction that keeps the image quality.
+    UserInterface,
+    /// Mode for still pictures.
+    StillPicture,
+    /// Mode for moving images, with the largest power savings.
+    MovingImage,
+}
+
+/// Gamma curve.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum GammaCurve {
+    /// Gamma curve set by the init sequence of the model.
+    #[default]
+    Default,
+    /// One of the gamma curves which are predefined by the controller.
+    Predefined(PredefinedGamma),
+    /// Custom gamma correction tables.
+    ///
+    /// The tables are written to the positive and negative gamma correction registers of the
+    /// controller, e.g. `PVGAMCTRL` and `NVGAMCTRL` for ST77xx controllers. Their format
+    /// depends on the controller, refer to its datasheet or to the values provided by the panel
+    /// vendor. Both tables must be [`GAMMA_TABLE_LEN`](Model::GAMMA_TABLE_LEN) bytes long.
+    ///
+    /// The GC9A01 uses two registers per table, the first 6 bytes of each 12 byte table are
+    /// written to `SET_GAMMA1`/`SET_GAMMA3` and the remaining bytes to `SET_GAMMA2`/`SET_GAMMA4`.
+    Custom {
+        /// Positive gamma correction table.
+        positive: &'static [u8],
+        /// Negative gamma correction table.
+        negative: &'static [u8],
+    },
+}
+
+/// Predefined gamma curve, selected with the Gamma Set (`0x26`) command.
+///
+/// The gamma values of the curves depend on the controller, e.g. 2.2, 1.8, 2.5 and 1.0 for
+/// the ST7789 and ILI9341.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PredefinedGamma {
+    /// Gamma curve 1 (GC0).
+    Curve1,
+    /// Gamma curve 2 (GC1).
+    Curve2,
+    /// Gamma curve 3 (GC2).
+    Curve3,
+    /// Gamma curve 4 (GC3).
+    Curve4,
+}
+
+/// Frame rate and porch settings.
+///
+/// The frame rate is set by the number of clocks per line (the line period, `RTNA`) and the
+/// number of lines in the vertical front and back porches. Larger values lower the frame rate,
+/// which reduces the power consumption but can cause flicker. The units and valid ranges
+/// depend on the controller:
+///
+/// | Controller  | Line period | Porches   | Frame rate                                            |
+/// |-------------|-------------|-----------|-------------------------------------------------------|
+/// | ST7789      | `0..=31`    | `1..=127` | 10 MHz / ((320 + porches) × (250 + 16 × line period)) |
+/// | ST7735s     | `0..=15`    | `1..=63`  | 850 kHz / ((40 + 2 × line period) × (162 + porches))  |
+/// | ILI9341/2C  | `16..=31`   | `2..=127` | 615 kHz / (line period × (320 + porches))             |
+/// | GC9A01      | `0..=15`    | -         | lower with larger line periods, see below             |
+///
+/// The GC9A01 doesn't support setting the porches. The line period is written to the `RTN` bits
+/// (3-0) of the Frame Rate register (`0xE8`), while bits 6-4 select the inversion mode and
+/// are kept at `0b011` from the init sequence, i.e. the register is set to
+/// `0x30 | line period`. The init sequence uses a line period of 4.
+///
+/// Other models don't support setting the frame rate.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameRate {
+    /// Line period in clocks per line.
+    pub line_period: u8,
+    /// Front and back porch in lines, `None` keeps the porches set by the init sequence.
+    pub porch: Option<(u8, u8)>,
+}
+
+impl FrameRate {
+    /// Creates a new frame rate setting with the given line period.
+    pub const fn new(line_period: u8) -> Self {
+        Self {
+            line_period,
+            porch: None,
+        }
+    }
+
+    /// Sets the front and back porch.
+    #[must_use]
+    pub const fn with_porch(mut self, front: u8, back: u8) -> Self {
+        self.porch = Some((front, back));
+        self
+    }
+}
+
 /// Subpixel order.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -22,6 +22,11 @@
 //! 4.  Once the scope ends, `RawFrameBuf` is dropped, releasing its borrow on the buffer.
 //! 5.  The now-populated buffer is passed to an async method on the display driver to be rendered.
 //!
+//! If only small parts of the frame change between updates, dirty tracking can be enabled
+//! with [`RawFrameBuf::with_dirty_tracking`]. The framebuffer then records the bounding box
+//! of all changed pixels, and [`Display::show_dirty`](crate::Display::show_dirty) only sends
+//! that area to the display.
+//!
 //! # Example
 //!
 //! ```
@@ -148,6 +153,8 @@ where
     buffer: BUF,
     width: usize,
     height: usize,
+    dirty_tracking: bool,
+    dirty: Option<(Point, Point)>,
     _phantom_color: core::marker::PhantomData<C>,
 }
 
@@ -173,10 +180,60 @@ where
             buffer,
             width,
             height,
+            dirty_tracking: false,
+            dirty: None,
             _phantom_color: core::marker::PhantomData,
         }
     }
 
+    /// Enables tracking of the area changed by drawing operations.
+    ///
+    /// The bounding box of all pixels changed since the last call to
+    /// [`take_dirty`](Self::take_dirty) is accumulated, which allows sending only the
+    /// changed part of the frame to the display, e.g. with
+    /// [`Display::show_dirty`](crate::Display::show_dirty).
+    #[must_use]
+    pub fn with_dirty_tracking(mut self) -> Self {
+        self.dirty_tracking = true;
+        self
+    }
+
+    /// Returns the area changed since the last call and resets the tracked area.
+    ///
+    /// Returns `None` if nothing was drawn. If dirty tracking isn't enabled, the whole
+    /// framebuffer is always returned.
+    pub fn take_dirty(&mut self) -> Option<Rectangle> {
+        if !self.dirty_tracking {
+            return Some(self.bounding_box());
+        }
+
+        self.dirty
+            .take()
+            .map(|(top_left, bottom_right)| Rectangle::with_corners(top_left, bottom_right))
+    }
+
+    /// Marks an area of the framebuffer as changed.
+    ///
+    /// This is only required after the buffer was modified directly, without using the
+    /// [`DrawTarget`] implementation.
+    pub fn mark_dirty(&mut self, area: &Rectangle) {
+        let area = area.intersection(&self.bounding_box());
+        if let Some(bottom_right) = area.bottom_right() {
+            self.extend_dirty(area.top_left, bottom_right);
+        }
+    }
+
+    fn extend_dirty(&mut self, top_left: Point, bottom_right: Point) {
+        if !self.dirty_tracking {
+            return;
+        }
+
+        self.dirty = Some(match self.dirty {
+            Some((min, max)) => (min.component_min(top_left), max.component_max(bottom_right)),
+            None => (top_left, bottom_right),
+        });
+    }
+
     /// Returns the width of the framebuffer in pixels.
     pub fn width(&self) -> usize {
         self.width
@@ -194,7 +251,10 @@ where
     }
 
     /// Returns the raw framebuffer data as a mutable byte slice.
+    ///
+    /// If dirty tracking is enabled, the whole framebuffer is marked as changed.
     pub fn as_mut_bytes(&mut self) -> &mut [u8] {
+        self.mark_dirty(&self.bounding_box());
         let expected_len = self.width * self.height * C::BYTES_PER_PIXEL;
         &mut self.buffer.as_mut_u8_slice()[0..expected_len]
     }
@@ -227,6 +287,7 @@ where
 
         let buffer_slice = self.buffer.as_mut_u8_slice();
         let active_buffer_len = self.width * self.height * C::BYTES_PER_PIXEL;
+        let mut dirty: Option<(Point, Point)> = None;
 
         for Pixel(coord, color) in pixels.into_iter() {
             if bounding_box.contains(coord) {
@@ -238,9 +299,18 @@ where
                 if byte_index + C::BYTES_PER_PIXEL <= active_buffer_len {
                     buffer_slice[byte_index..byte_index + C::BYTES_PER_PIXEL]
                         .copy_from_slice(color_bytes.as_ref());
+
+                    dirty = Some(match dirty {
+                        Some((min, max)) => (min.component_min(coord), max.component_max(coord)),
+                        None => (coord, coord),
+                    });
                 }
             }
         }
+
+        if let Some((top_left, bottom_right)) = dirty {
+            self.extend_dirty(top_left, bottom_right);
+        }
         Ok(())
     }
 
@@ -265,6 +335,8 @@ where
                 chunk.copy_from_slice(color_bytes);
             }
         }
+
+        self.mark_dirty(&self.bounding_box());
         Ok(())
     }
 
@@ -288,6 +360,78 @@ where
                     .copy_from_slice(color_bytes);
             }
         }
+
+        self.mark_dirty(&drawable_area);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{
+        pixelcolor::Rgb565,
+        primitives::{Line, PrimitiveStyle},
+    };
+
+    use super::*;
+
+    #[test]
+    fn dirty_tracking_disabled() {
+        let mut buffer = [0u8; 8 * 4 * 2];
+        let mut fbuf = RawFrameBuf::<Rgb565, _>::new(&mut buffer[..], 8, 4);
+
+        assert_eq!(fbuf.take_dirty(), Some(fbuf.bounding_box()));
+    }
+
+    #[test]
+    fn dirty_tracking_accumulates_bounding_box() {
+        let mut buffer = [0u8; 8 * 4 * 2];
+        let mut fbuf = RawFrameBuf::<Rgb565, _>::new(&mut buffer[..], 8, 4).with_dirty_tracking();
+        assert_eq!(fbuf.take_dirty(), None);
+
+        Pixel(Point::new(1, 2), Rgb565::RED)
+            .draw(&mut fbuf)
+            .unwrap();
+        fbuf.fill_solid(
+            &Rectangle::new(Point::new(4, 0), Size::new(2, 2)),
+            Rgb565::GREEN,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fbuf.take_dirty(),
+            Some(Rectangle::with_corners(Point::new(1, 0), Point::new(5, 2)))
+        );
+        assert_eq!(fbuf.take_dirty(), None);
+    }
+
+    #[test]
+    fn dirty_tracking_ignores_clipped_pixels() {
+        let mut buffer = [0u8; 8 * 4 * 2];
+        let mut fbuf = RawFrameBuf::<Rgb565, _>::new(&mut buffer[..], 8, 4).with_dirty_tracking();
+
+        Line::new(Point::new(6, 3), Point::new(20, 3))
+            .into_styled(PrimitiveStyle::with_stroke(Rgb565::BLUE, 1))
+            .draw(&mut fbuf)
+            .unwrap();
+        fbuf.fill_solid(
+            &Rectangle::new(Point::new(-5, -5), Size::new(2, 2)),
+            Rgb565::GREEN,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fbuf.take_dirty(),
+            Some(Rectangle::with_corners(Point::new(6, 3), Point::new(7, 3)))
+        );
+    }
+
+    #[test]
+    fn dirty_tracking_clear() {
+        let mut buffer = [0u8; 8 * 4 * 2];
+        let mut fbuf = RawFrameBuf::<Rgb565, _>::new(&mut buffer[..], 8, 4).with_dirty_tracking();
+
+        fbuf.clear(Rgb565::BLACK).unwrap();
+        assert_eq!(fbuf.take_dirty(), Some(fbuf.bounding_box()));
+    }
+}
@@ -0,0 +1,254 @@
+//! Sleep mode with compile time state tracking.
+//!
+//! [`Display::sleep`] consumes the display and returns a [`SleepingDisplay`], which only
+//! allows to [`wake`](SleepingDisplay::wake) the display again. This turns drawing to a
+//! sleeping display into a compile error. [`DisplayState`] can be used if a single type is
+//! required for both states, e.g. for a field in a struct.
+//!
+//! If a transition fails, the display is returned in a [`SleepError`], so that the transition
+//! can be retried or the hardware resources can be released.
+//!
+//! # Example
+//!
+//! ```
+//! # tokio_test::block_on(async {
+//! # let display = lcd_async::_mock::new_mock_display().await;
+//! # let mut delay = lcd_async::_mock::MockDelay;
+//! let sleeping = display.sleep(&mut delay).await.unwrap();
+//! // `sleeping.show_raw_data(...)` doesn't compile
+//! let mut display = sleeping.wake(&mut delay).await.unwrap();
+//! display.clear(embedded_graphics::pixelcolor::Rgb565::new(0, 0, 0)).await.unwrap();
+//! # });
+//! ```
+
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::delay::DelayNs;
+
+use crate::{backlight::Backlight, interface::Interface, models::Model, Display, DisplayError};
+
+/// Error returned by the sleep and wake methods.
+///
+/// Contains the display in the state before the failed transition, so that it isn't lost with
+/// the hardware resources it owns.
+pub struct SleepError<D, E> {
+    /// The display.
+    pub display: D,
+    /// The error which occurred during the transition.
+    pub error: E,
+}
+
+impl<D, E: core::fmt::Debug> core::fmt::Debug for SleepError<D, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SleepError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<D, E> SleepError<D, E> {
+    /// Returns a function which maps the display of the error.
+    pub(crate) fn map_display<D2>(
+        f: impl FnOnce(D) -> D2,
+    ) -> impl FnOnce(Self) -> SleepError<D2, E> {
+        move |error| SleepError {
+            display: f(error.display),
+            error: error.error,
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<D, E: defmt::Format> defmt::Format for SleepError<D, E> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "SleepError {{ error: {} }}", self.error)
+    }
+}
+
+/// A display which is in sleep mode.
+///
+/// Returned by [`Display::sleep`] and [`AnyDisplay::sleep`](crate::AnyDisplay::sleep).
+/// The only available operation is `wake`, which returns the awake display. Use
+/// [`into_inner`](Self::into_inner) to release the hardware resources without waking the
+/// display.
+///
+/// ```compile_fail
+/// # tokio_test::block_on(async {
+/// # let display = lcd_async::_mock::new_mock_display().await;
+/// # let mut delay = lcd_async::_mock::MockDelay;
+/// let mut sleeping = display.sleep(&mut delay).await.unwrap();
+/// sleeping.show_raw_data(0, 0, 1, 1, &[0u8; 2]).await.unwrap();
+/// # });
+/// ```
+pub struct SleepingDisplay<D> {
+    pub(crate) display: D,
+}
+
+impl<D> SleepingDisplay<D> {
+    /// Returns the display without waking it up.
+    ///
+    /// The controller stays in sleep mode. This is intended to release the hardware
+    /// resources, e.g. with [`Display::release`]. Use `wake` to continue using the display.
+    pub fn into_inner(self) -> D {
+        self.display
+    }
+}
+
+impl<DI, M, RST, TE, BL> Display<DI, M, RST, TE, BL>
+where
+    DI: Interface,
+    M: Model,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    ///
+    /// Puts the display to sleep, reducing power consumption.
+    /// Returns a [`SleepingDisplay`], which needs to be woken up before issuing other commands.
+    ///
+    /// The backlight is turned off before the display enters sleep mode.
+    /// If an error occurs, the display is returned in the [`SleepError`].
+    ///
+    pub async fn sleep<D: DelayNs>(
+        mut self,
+        delay: &mut D,
+    ) -> Result<SleepingDisplay<Self>, SleepError<Self, DisplayError<DI::Error, BL::Error>>> {
+        match self.enter_sleep(delay).await {
+            Ok(()) => Ok(SleepingDisplay { display: self }),
+            Err(error) => Err(SleepError {
+                display: self,
+                error,
+            }),
+        }
+    }
+
+    pub(crate) async fn enter_sleep<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), DisplayError<DI::Error, BL::Error>> {
+        self.backlight
+            .set_level(0)
+            .map_err(DisplayError::Backlight)?;
+        M::sleep(&mut self.di, delay)
+            .await
+            .map_err(DisplayError::Interface)
+    }
+
+    pub(crate) async fn exit_sleep<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<(), DisplayError<DI::Error, BL::Error>> {
+        M::wake(&mut self.di, delay)
+            .await
+            .map_err(DisplayError::Interface)?;
+        self.backlight
+            .set_level(self.backlight_level)
+            .map_err(DisplayError::Backlight)
+    }
+}
+
+impl<DI, M, RST, TE, BL> SleepingDisplay<Display<DI, M, RST, TE, BL>>
+where
+    DI: Interface,
+    M: Model,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    ///
+    /// Wakes the display after it's been set to sleep via [`Display::sleep`].
+    ///
+    /// The backlight is restored to its previous level after the display woke up.
+    /// If an error occurs, the sleeping display is returned in the [`SleepError`].
+    ///
+    pub async fn wake<D: DelayNs>(
+        mut self,
+        delay: &mut D,
+    ) -> Result<Display<DI, M, RST, TE, BL>, SleepError<Self, DisplayError<DI::Error, BL::Error>>>
+    {
+        match self.display.exit_sleep(delay).await {
+            Ok(()) => Ok(self.display),
+            Err(error) => Err(SleepError {
+                display: self,
+                error,
+            }),
+        }
+    }
+}
+
+/// A display which is either awake or sleeping.
+///
+/// This type tracks the sleep state at runtime, for code which needs a single type for both
+/// states. Use [`awake_mut`](Self::awake_mut) to access the display while it's awake.
+pub enum DisplayState<D> {
+    /// The display is awake.
+    Awake(D),
+    /// The display is sleeping.
+    Sleeping(SleepingDisplay<D>),
+}
+
+impl<D> DisplayState<D> {
+    /// Returns `true` if the display is sleeping.
+    pub fn is_sleeping(&self) -> bool {
+        matches!(self, Self::Sleeping(_))
+    }
+
+    /// Returns the display if it's awake.
+    pub fn awake_mut(&mut self) -> Option<&mut D> {
+        match self {
+            Self::Awake(display) => Some(display),
+            Self::Sleeping(_) => None,
+        }
+    }
+}
+
+impl<D> From<D> for DisplayState<D> {
+    fn from(display: D) -> Self {
+        Self::Awake(display)
+    }
+}
+
+impl<D> From<SleepingDisplay<D>> for DisplayState<D> {
+    fn from(display: SleepingDisplay<D>) -> Self {
+        Self::Sleeping(display)
+    }
+}
+
+impl<DI, M, RST, TE, BL> DisplayState<Display<DI, M, RST, TE, BL>>
+where
+    DI: Interface,
+    M: Model,
+    RST: OutputPin,
+    BL: Backlight,
+{
+    /// Puts the display to sleep, see [`Display::sleep`].
+    ///
+    /// Does nothing if the display is already sleeping.
+    pub async fn sleep<D: DelayNs>(
+        self,
+        delay: &mut D,
+    ) -> Result<Self, SleepError<Self, DisplayError<DI::Error, BL::Error>>> {
+        match self {
+            Self::Awake(display) => display
+                .sleep(delay)
+                .await
+                .map(Self::Sleeping)
+                .map_err(SleepError::map_display(Self::Awake)),
+            Self::Sleeping(_) => Ok(self),
+        }
+    }
+
+    /// Wakes the display, see [`SleepingDisplay::wake`].
+    ///
+    /// Does nothing if the display is already awake.
+    pub async fn wake<D: DelayNs>(
+        self,
+        delay: &mut D,
+    ) -> Result<Self, SleepError<Self, DisplayError<DI::Error, BL::Error>>> {
+        match self {
+            Self::Awake(_) => Ok(self),
+            Self::Sleeping(display) => display
+                .wake(delay)
+                .await
+                .map(Self::Awake)
+                .map_err(SleepError::map_display(Self::Sleeping)),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Double buffering with two framebuffers.
+//!
+//! A [`SwapChain`] owns two [`RawFrameBuf`]s: a front buffer, which holds the last completed
+//! frame, and a back buffer, which is used to draw the next frame. [`SwapChain::split`] borrows
+//! both buffers at the same time, which allows the front buffer to be sent to the display while
+//! the next frame is drawn into the back buffer. Once both are done, [`SwapChain::swap`]
+//! exchanges the buffers.
+//!
+//! The swap chain doesn't require `alloc` and works with any buffer backend, e.g. two
+//! `static` arrays managed by [`static_cell`](https://docs.rs/static_cell).
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use embassy_futures::join::join;
+//! use lcd_async::swap_chain::SwapChain;
+//! use static_cell::ConstStaticCell;
+//!
+//! // `ConstStaticCell` places the buffers in static memory without copying them on the stack.
+//! static FRONT: ConstStaticCell<[u8; 320 * 240 * 2]> = ConstStaticCell::new([0; 320 * 240 * 2]);
+//! static BACK: ConstStaticCell<[u8; 320 * 240 * 2]> = ConstStaticCell::new([0; 320 * 240 * 2]);
+//!
+//! let mut chain = SwapChain::<Rgb565, _>::new(
+//!     FRONT.take().as_mut_slice(),
+//!     BACK.take().as_mut_slice(),
+//!     320,
+//!     240,
+//! );
+//!
+//! loop {
+//!     let (front, back) = chain.split();
+//!     // Send the previous frame while the next one is drawn.
+//!     let (result, _) = join(front.present(&mut display), async {
+//!         draw_scene(back);
+//!     })
+//!     .await;
+//!     result.unwrap();
+//!
+//!     chain.swap();
+//! }
+//! ```
+
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{Interface, PixelWord},
+    models::Model,
+    raw_framebuf::{IntoRawBytes, RawBufferBackendMut, RawFrameBuf},
+    Display,
+};
+
+/// Two framebuffers which are alternately drawn to and sent to the display.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct SwapChain<C, BUF>
+where
+    C: IntoRawBytes,
+    BUF: RawBufferBackendMut,
+{
+    buffers: [RawFrameBuf<C, BUF>; 2],
+    front: usize,
+}
+
+impl<C, BUF> SwapChain<C, BUF>
+where
+    C: IntoRawBytes,
+    BUF: RawBufferBackendMut,
+{
+    /// Creates a new swap chain from two buffers of the same size.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the buffers is smaller than `width * height * C::BYTES_PER_PIXEL`.
+    pub fn new(front: BUF, back: BUF, width: usize, height: usize) -> Self {
+        Self {
+            buffers: [
+                RawFrameBuf::new(front, width, height),
+                RawFrameBuf::new(back, width, height),
+            ],
+            front: 0,
+        }
+    }
+
+    /// Returns the front buffer, which holds the last completed frame.
+    pub fn front(&self) -> &RawFrameBuf<C, BUF> {
+        &self.buffers[self.front]
+    }
+
+    /// Returns the back buffer, which is used to draw the next frame.
+    ///
+    /// After a [`swap`](Self::swap) the back buffer contains the frame before the last
+    /// one, not the last frame.
+    pub fn back_mut(&mut self) -> &mut RawFrameBuf<C, BUF> {
+        &mut self.buffers[1 - self.front]
+    }
+
+    /// Borrows the front buffer for presenting and the back buffer for drawing at the same time.
+    pub fn split(&mut self) -> (FrontBuffer<'_, C, BUF>, &mut RawFrameBuf<C, BUF>) {
+        let [first, second] = &mut self.buffers;
+        let (front, back) = if self.front == 0 {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        (FrontBuffer { buffer: front }, back)
+    }
+
+    /// Exchanges the front and back buffers.
+    ///
+    /// The back buffer becomes the front buffer, which will be sent by the next
+    /// [`present`](FrontBuffer::present).
+    pub fn swap(&mut self) {
+        self.front = 1 - self.front;
+    }
+
+    /// Sends the front buffer to the display.
+    ///
+    /// This is a shortcut for `chain.split().0.present(display)`.
+    pub async fn present<DI, M, RST, TE, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, TE, BL>,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DI::Word: PixelWord,
+        M: Model<ColorFormat = C>,
+        RST: OutputPin,
+    {
+        self.split().0.present(display).await
+    }
+
+    /// Consumes the swap chain and returns the front and back framebuffers.
+    pub fn release(self) -> (RawFrameBuf<C, BUF>, RawFrameBuf<C, BUF>) {
+        let [first, second] = self.buffers;
+        if self.front == 0 {
+            (first, second)
+        } else {
+            (second, first)
+        }
+    }
+}
+
+/// The front buffer of a [`SwapChain`], borrowed for presenting.
+pub struct FrontBuffer<'a, C, BUF>
+where
+    C: IntoRawBytes,
+    BUF: RawBufferBackendMut,
+{
+    buffer: &'a RawFrameBuf<C, BUF>,
+}
+
+impl<C, BUF> FrontBuffer<'_, C, BUF>
+where
+    C: IntoRawBytes,
+    BUF: RawBufferBackendMut,
+{
+    /// Returns the front framebuffer.
+    pub fn framebuffer(&self) -> &RawFrameBuf<C, BUF> {
+        self.buffer
+    }
+
+    /// Sends the whole front buffer to the display, starting at the top left corner.
+    pub async fn present<DI, M, RST, TE, BL>(
+        &self,
+        display: &mut Display<DI, M, RST, TE, BL>,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DI::Word: PixelWord,
+        M: Model<ColorFormat = C>,
+        RST: OutputPin,
+    {
+        display
+            .show_raw_bytes(
+                0,
+                0,
+                self.buffer.width() as u16,
+                self.buffer.height() as u16,
+                self.buffer.as_bytes(),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{pixelcolor::Rgb565, prelude::*};
+
+    use super::*;
+    use crate::{
+        models::ST7789,
+        testing::{RecordingDelay, RecordingInterface, Transfer},
+        Builder,
+    };
+
+    #[test]
+    fn split_and_swap() {
+        let mut front = [0u8; 2 * 2 * 2];
+        let mut back = [0u8; 2 * 2 * 2];
+        let mut chain = SwapChain::<Rgb565, _>::new(&mut front[..], &mut back[..], 2, 2);
+
+        let (front, back) = chain.split();
+        back.clear(Rgb565::WHITE).unwrap();
+        assert_eq!(front.framebuffer().as_bytes(), [0; 8]);
+
+        chain.swap();
+        assert_eq!(chain.front().as_bytes(), [0xFF; 8]);
+        assert_eq!(chain.back_mut().as_bytes(), [0; 8]);
+
+        let (front, back) = chain.release();
+        assert_eq!(front.as_bytes(), [0xFF; 8]);
+        assert_eq!(back.as_bytes(), [0; 8]);
+    }
+
+    #[test]
+    fn present_sends_front_buffer() {
+        tokio_test::block_on(async {
+            let mut display = Builder::new(ST7789, RecordingInterface::new())
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+
+            let mut front = [0u8; 2 * 2 * 2];
+            let mut back = [0u8; 2 * 2 * 2];
+            let mut chain = SwapChain::<Rgb565, _>::new(&mut front[..], &mut back[..], 2, 2);
+
+            let (front, back) = chain.split();
+            let present = front.present(&mut display);
+            back.clear(Rgb565::WHITE).unwrap();
+            present.await.unwrap();
+
+            chain.swap();
+            chain.present(&mut display).await.unwrap();
+
+            let (di, _, _) = display.release();
+            let data: alloc::vec::Vec<_> = di
+                .log()
+                .iter()
+                .filter_map(|transfer| match transfer {
+                    Transfer::Data(data) => Some(data.as_slice()),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(data, [&[0; 8], &[0xFF; 8]]);
+        });
+    }
+}
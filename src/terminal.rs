@@ -0,0 +1,623 @@
+//! Scrolling text terminal.
+//!
+//! A [`Terminal`] renders monospace text into a framebuffer which only holds a single line
+//! of text. Completed lines are never redrawn: when the display is full, the hardware vertical
+//! scrolling of the controller (`VSCRDEF` and `VSCRSADD`) is used to move the visible area
+//! down by one line and the next line is written to the frame memory rows which were
+//! scrolled out at the top. This makes the terminal well suited for log output, e.g. during
+//! the boot process.
+//!
+//! The foreground and background colors can be changed with ANSI SGR escape sequences, e.g.
+//! `"\x1b[31m"` for red text and `"\x1b[0m"` to reset the colors. Other escape sequences
+//! are ignored.
+//!
+//! # Example
+//!
+//! ```
+//! use embedded_graphics::mono_font::ascii::FONT_6X10;
+//! use lcd_async::terminal::Terminal;
+//!
+//! # tokio_test::block_on(async {
+//! # let mut display = lcd_async::_mock::new_mock_display().await;
+//! // one line of 240 Rgb565 pixels in a 10 pixel high font
+//! let mut buffer = [0u8; 240 * 10 * 2];
+//! let mut terminal = Terminal::new(&display, &FONT_6X10, &mut buffer[..]);
+//!
+//! terminal.init(&mut display).await.unwrap();
+//! terminal
+//!     .write_str(&mut display, "booting...\n\x1b[32mOK\x1b[0m\n")
+//!     .await
+//!     .unwrap();
+//! # });
+//! ```
+
+use embedded_graphics::{
+    mono_font::{MonoFont, MonoTextStyleBuilder},
+    pixelcolor::{Rgb888, RgbColor},
+    prelude::*,
+    text::{Baseline, Text},
+};
+use embedded_hal::digital::OutputPin;
+
+use crate::{
+    interface::{Interface, PixelWord},
+    models::Model,
+    options::Rotation,
+    raw_framebuf::{IntoRawBytes, RawBufferBackendMut, RawFrameBuf},
+    Display,
+};
+
+/// Maximum number of parameters in an escape sequence, additional parameters are ignored.
+const MAX_PARAMETERS: usize = 8;
+
+/// Number of columns between tab stops.
+const TAB_WIDTH: u16 = 8;
+
+/// Text terminal which uses hardware scrolling.
+///
+/// See the [module-level documentation](self) for more information.
+pub struct Terminal<'a, C, BUF>
+where
+    C: IntoRawBytes,
+    BUF: RawBufferBackendMut,
+{
+    line: RawFrameBuf<C, BUF>,
+    font: &'a MonoFont<'a>,
+    /// Display size in pixels.
+    size: (u16, u16),
+    /// First frame memory row of the scroll area.
+    scroll_top: u16,
+    /// Frame memory rows below the scroll area.
+    scroll_bottom: u16,
+    /// Scroll area row which is shown at the top of the display.
+    scroll: u16,
+    /// Scroll area row of the current line.
+    row: u16,
+    /// Display row of the current line.
+    position: u16,
+    column: u16,
+    foreground: C,
+    background: C,
+    default_foreground: C,
+    default_background: C,
+    escape: Escape,
+    dirty: bool,
+}
+
+impl<'a, C, BUF> Terminal<'a, C, BUF>
+where
+    C: IntoRawBytes + RgbColor + From<Rgb888>,
+    BUF: RawBufferBackendMut,
+{
+    /// Creates a new terminal for a display.
+    ///
+    /// The buffer must hold one line of text, which requires
+    /// `display_width * font_height * C::BYTES_PER_PIXEL` bytes. The text is drawn in white on
+    /// a black background by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the display isn't in the default orientation, because hardware scrolling
+    /// always moves the rows of the default orientation, if the font is taller than the display
+    /// or if the buffer is too small.
+    pub fn new<DI, M, RST, TE, BL>(
+        display: &Display<DI, M, RST, TE, BL>,
+        font: &'a MonoFont<'a>,
+        buffer: BUF,
+    ) -> Self
+    where
+        DI: Interface,
+        M: Model<ColorFormat = C>,
+        RST: OutputPin,
+    {
+        assert!(
+            display.options.orientation.rotation == Rotation::Deg0,
+            "the terminal requires the default display orientation"
+        );
+
+        let size = display.options.display_size();
+        assert!(
+            font.character_size.height <= u32::from(size.1),
+            "the font is taller than the display"
+        );
+        let scroll_top = display.options.display_offset.1;
+        let line_height = font.character_size.height as usize;
+
+        Self {
+            line: RawFrameBuf::new(buffer, usize::from(size.0), line_height),
+            font,
+            size,
+            scroll_top,
+            scroll_bottom: M::FRAMEBUFFER_SIZE.1.saturating_sub(scroll_top + size.1),
+            scroll: 0,
+            row: 0,
+            position: 0,
+            column: 0,
+            foreground: C::WHITE,
+            background: C::BLACK,
+            default_foreground: C::WHITE,
+            default_background: C::BLACK,
+            escape: Escape::None,
+            dirty: false,
+        }
+    }
+
+    /// Sets the default text and background colors.
+    ///
+    /// The colors are used for text written after this call and are restored by the
+    /// `"\x1b[0m"` escape sequence.
+    #[must_use]
+    pub fn with_colors(mut self, foreground: C, background: C) -> Self {
+        self.foreground = foreground;
+        self.background = background;
+        self.default_foreground = foreground;
+        self.default_background = background;
+        self
+    }
+
+    /// Returns the number of text columns.
+    pub fn columns(&self) -> u16 {
+        (u32::from(self.size.0) / self.character_width()) as u16
+    }
+
+    /// Returns the number of text lines which are completely visible.
+    pub fn lines(&self) -> u16 {
+        self.size.1 / self.line_height()
+    }
+
+    /// Sets up the scroll area and clears the display.
+    ///
+    /// This must be called before text is written, and again after the scroll area or the
+    /// display contents were changed by other means.
+    pub async fn init<DI, M, RST, TE, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, TE, BL>,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DI::Word: PixelWord,
+        M: Model<ColorFormat = C>,
+        RST: OutputPin,
+    {
+        display
+            .set_vertical_scroll_region(self.scroll_top, self.scroll_bottom)
+            .await?;
+        display.set_vertical_scroll_offset(self.scroll_top).await?;
+        display.clear(self.default_background).await?;
+
+        self.scroll = 0;
+        self.row = 0;
+        self.position = 0;
+        self.column = 0;
+        self.foreground = self.default_foreground;
+        self.background = self.default_background;
+        self.escape = Escape::None;
+        self.clear_line();
+        self.dirty = false;
+
+        Ok(())
+    }
+
+    /// Writes a string to the terminal.
+    ///
+    /// Lines which are longer than the display are wrapped. Besides printable characters,
+    /// `\n`, `\r`, `\t` and ANSI SGR color sequences are supported. The current line is sent
+    /// to the display before this method returns, even if it isn't terminated by a newline.
+    pub async fn write_str<DI, M, RST, TE, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, TE, BL>,
+        text: &str,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DI::Word: PixelWord,
+        M: Model<ColorFormat = C>,
+        RST: OutputPin,
+    {
+        for c in text.chars() {
+            match self.escape.advance(c) {
+                Step::Print => {}
+                Step::Consumed => continue,
+                Step::Sgr(parameters, count) => {
+                    self.select_graphic_rendition(&parameters[..count]);
+                    continue;
+                }
+            }
+
+            match c {
+                '\n' => self.new_line(display).await?,
+                '\r' => self.column = 0,
+                '\t' => {
+                    let column = (self.column / TAB_WIDTH + 1) * TAB_WIDTH;
+                    self.column = column.min(self.columns());
+                }
+                c if c.is_control() => {}
+                c => {
+                    if self.column >= self.columns() {
+                        self.new_line(display).await?;
+                    }
+                    self.draw_char(c);
+                    self.column += 1;
+                }
+            }
+        }
+
+        self.flush(display).await
+    }
+
+    /// Consumes the terminal and returns the line framebuffer.
+    pub fn release(self) -> RawFrameBuf<C, BUF> {
+        self.line
+    }
+
+    fn character_width(&self) -> u32 {
+        self.font.character_size.width + self.font.character_spacing
+    }
+
+    fn line_height(&self) -> u16 {
+        self.font.character_size.height as u16
+    }
+
+    fn draw_char(&mut self, c: char) {
+        let style = MonoTextStyleBuilder::new()
+            .font(self.font)
+            .text_color(self.foreground)
+            .background_color(self.background)
+            .build();
+        let position = Point::new((u32::from(self.column) * self.character_width()) as i32, 0);
+
+        let mut utf8 = [0; 4];
+        // drawing into a RawFrameBuf can't fail
+        let _ = Text::with_baseline(c.encode_utf8(&mut utf8), position, style, Baseline::Top)
+            .draw(&mut self.line);
+        self.dirty = true;
+    }
+
+    fn clear_line(&mut self) {
+        // drawing into a RawFrameBuf can't fail
+        let _ = self.line.clear(self.default_background);
+    }
+
+    /// Moves to the start of the next line, scrolling the display if necessary.
+    async fn new_line<DI, M, RST, TE, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, TE, BL>,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DI::Word: PixelWord,
+        M: Model<ColorFormat = C>,
+        RST: OutputPin,
+    {
+        self.flush(display).await?;
+
+        let height = self.size.1;
+        let line_height = self.line_height();
+        self.row = (self.row + line_height) % height;
+        self.column = 0;
+        self.clear_line();
+
+        let bottom = self.position + 2 * line_height;
+        if bottom > height {
+            // The old contents of the new line are cleared before they are scrolled in.
+            self.dirty = true;
+            self.flush(display).await?;
+
+            self.scroll = (self.scroll + bottom - height) % height;
+            self.position = height - line_height;
+            display
+                .set_vertical_scroll_offset(self.scroll_top + self.scroll)
+                .await?;
+        } else {
+            self.position += line_height;
+        }
+
+        Ok(())
+    }
+
+    /// Sends the current line to the display.
+    async fn flush<DI, M, RST, TE, BL>(
+        &mut self,
+        display: &mut Display<DI, M, RST, TE, BL>,
+    ) -> Result<(), DI::Error>
+    where
+        DI: Interface,
+        DI::Word: PixelWord,
+        M: Model<ColorFormat = C>,
+        RST: OutputPin,
+    {
+        if !self.dirty {
+            return Ok(());
+        }
+        self.dirty = false;
+
+        let (width, height) = self.size;
+        let line_height = self.line_height();
+        let bytes = self.line.as_bytes();
+
+        // A line which doesn't fit below its first row wraps around to the top of the
+        // scroll area.
+        let rows = line_height.min(height - self.row);
+        let split = usize::from(rows) * usize::from(width) * C::BYTES_PER_PIXEL;
+        display
+            .show_raw_bytes(0, self.row, width, rows, &bytes[..split])
+            .await?;
+        if rows < line_height {
+            display
+                .show_raw_bytes(0, 0, width, line_height - rows, &bytes[split..])
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    fn select_graphic_rendition(&mut self, parameters: &[u16]) {
+        // An empty parameter list is equivalent to a reset.
+        if parameters.is_empty() {
+            self.foreground = self.default_foreground;
+            self.background = self.default_background;
+        }
+
+        for &parameter in parameters {
+            match parameter {
+                0 => {
+                    self.foreground = self.default_foreground;
+                    self.background = self.default_background;
+                }
+                30..=37 => self.foreground = ansi_color(parameter - 30, false),
+                39 => self.foreground = self.default_foreground,
+                40..=47 => self.background = ansi_color(parameter - 40, false),
+                49 => self.background = self.default_background,
+                90..=97 => self.foreground = ansi_color(parameter - 90, true),
+                100..=107 => self.background = ansi_color(parameter - 100, true),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Returns one of the 16 ANSI colors.
+fn ansi_color<C: From<Rgb888>>(index: u16, bright: bool) -> C {
+    let (low, high) = if bright { (85, 255) } else { (0, 170) };
+    let channel = |bit: u16| if index & bit != 0 { high } else { low };
+
+    let color = match (index, bright) {
+        // yellow is traditionally shown as brown
+        (3, false) => Rgb888::new(170, 85, 0),
+        (7, false) => Rgb888::new(170, 170, 170),
+        _ => Rgb888::new(channel(1), channel(2), channel(4)),
+    };
+
+    color.into()
+}
+
+/// State of the escape sequence parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Escape {
+    None,
+    /// An escape character was received.
+    Start,
+    /// Inside of a control sequence (`ESC [`).
+    Csi {
+        parameters: [u16; MAX_PARAMETERS],
+        count: usize,
+    },
+}
+
+/// Result of feeding a character into the escape sequence parser.
+#[derive(Debug, PartialEq, Eq)]
+enum Step {
+    /// The character isn't part of an escape sequence.
+    Print,
+    /// The character was consumed by the parser.
+    Consumed,
+    /// A complete SGR sequence with the given number of parameters was received.
+    Sgr([u16; MAX_PARAMETERS], usize),
+}
+
+impl Escape {
+    /// Feeds a character into the parser.
+    fn advance(&mut self, c: char) -> Step {
+        match self {
+            Escape::None if c == '\x1b' => *self = Escape::Start,
+            Escape::None => return Step::Print,
+            Escape::Start => {
+                *self = if c == '[' {
+                    Escape::Csi {
+                        parameters: [0; MAX_PARAMETERS],
+                        count: 0,
+                    }
+                } else {
+                    Escape::None
+                };
+            }
+            Escape::Csi { parameters, count } => match c {
+                '0'..='9' => {
+                    if *count == 0 {
+                        *count = 1;
+                    }
+                    if let Some(parameter) = parameters.get_mut(*count - 1) {
+                        let digit = c as u16 - '0' as u16;
+                        *parameter = parameter.saturating_mul(10).saturating_add(digit);
+                    }
+                }
+                // an empty first parameter is 0
+                ';' => *count = (*count).max(1) + 1,
+                // final byte of the sequence
+                '\x40'..='\x7e' => {
+                    let step = if c == 'm' {
+                        Step::Sgr(*parameters, (*count).min(MAX_PARAMETERS))
+                    } else {
+                        Step::Consumed
+                    };
+                    *self = Escape::None;
+                    return step;
+                }
+                _ => {}
+            },
+        }
+
+        Step::Consumed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::{mono_font::ascii::FONT_6X10, pixelcolor::Rgb565};
+
+    use alloc::vec;
+
+    use super::*;
+    use crate::{
+        models::ST7789,
+        testing::{RecordingDelay, VirtualPanel},
+        Builder, NoResetPin,
+    };
+
+    async fn panel_display(height: u16) -> Display<VirtualPanel, ST7789, NoResetPin> {
+        Builder::new(ST7789, VirtualPanel::for_model::<ST7789>())
+            .display_size(24, height)
+            .init(&mut RecordingDelay::new())
+            .await
+            .unwrap()
+    }
+
+    /// Returns how an ANSI color is shown by the panel after the conversion to Rgb565.
+    fn shown(color: Rgb888) -> Rgb888 {
+        let color = Rgb565::from(color);
+        let (r, g, b) = (color.r(), color.g(), color.b());
+        Rgb888::new(r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2)
+    }
+
+    /// Returns `true` if a row of the visible area contains any pixels of the given color.
+    fn row_contains(panel: &VirtualPanel, y: u16, color: Rgb888) -> bool {
+        (0..panel.visible_size().0).any(|x| panel.pixel(x, y) == Some(color))
+    }
+
+    /// Returns `true` if any pixel in the rows `rows` has the given color.
+    fn rows_contain(panel: &VirtualPanel, rows: core::ops::Range<u16>, color: Rgb888) -> bool {
+        rows.into_iter().any(|y| row_contains(panel, y, color))
+    }
+
+    #[test]
+    fn sgr_parameters() {
+        let mut escape = Escape::None;
+        let mut printed = alloc::string::String::new();
+        let mut sequences = alloc::vec::Vec::new();
+        for c in "a\x1b[1;31mb\x1b[mc\x1b[;42m\x1b[2Jd".chars() {
+            match escape.advance(c) {
+                Step::Print => printed.push(c),
+                Step::Consumed => {}
+                Step::Sgr(parameters, count) => sequences.push(parameters[..count].to_vec()),
+            }
+        }
+
+        assert_eq!(printed, "abcd");
+        assert_eq!(sequences, [vec![1, 31], vec![], vec![0, 42]]);
+    }
+
+    #[test]
+    fn writes_lines_without_scrolling() {
+        tokio_test::block_on(async {
+            let mut display = panel_display(30).await;
+            let mut buffer = [0u8; 24 * 10 * 2];
+            let mut terminal = Terminal::new(&display, &FONT_6X10, &mut buffer[..]);
+            assert_eq!((terminal.columns(), terminal.lines()), (4, 3));
+
+            terminal.init(&mut display).await.unwrap();
+            terminal
+                .write_str(&mut display, "AB\n\x1b[31mCD")
+                .await
+                .unwrap();
+
+            let (panel, _, _) = display.release();
+            assert!(rows_contain(&panel, 0..10, Rgb888::WHITE));
+            assert!(!rows_contain(&panel, 10..20, Rgb888::WHITE));
+            assert!(rows_contain(&panel, 10..20, shown(Rgb888::new(170, 0, 0))));
+            assert!(!rows_contain(&panel, 20..30, Rgb888::WHITE));
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "the font is taller than the display")]
+    fn font_taller_than_display() {
+        tokio_test::block_on(async {
+            let display = panel_display(8).await;
+            let mut buffer = [0u8; 24 * 10 * 2];
+            let _ = Terminal::new(&display, &FONT_6X10, &mut buffer[..]);
+        });
+    }
+
+    #[test]
+    fn scrolls_when_full() {
+        tokio_test::block_on(async {
+            let mut display = panel_display(30).await;
+            let mut buffer = [0u8; 24 * 10 * 2];
+            let mut terminal = Terminal::new(&display, &FONT_6X10, &mut buffer[..]);
+
+            terminal.init(&mut display).await.unwrap();
+            terminal
+                .write_str(&mut display, "\x1b[31mA\n\x1b[32mB\n\x1b[34mC\n\x1b[33mD")
+                .await
+                .unwrap();
+
+            let (panel, _, _) = display.release();
+            let red = shown(Rgb888::new(170, 0, 0));
+            let green = shown(Rgb888::new(0, 170, 0));
+            let blue = shown(Rgb888::new(0, 0, 170));
+            let brown = shown(Rgb888::new(170, 85, 0));
+
+            // the first line was scrolled out
+            assert!(!rows_contain(&panel, 0..30, red));
+            assert!(rows_contain(&panel, 0..10, green));
+            assert!(rows_contain(&panel, 10..20, blue));
+            assert!(rows_contain(&panel, 20..30, brown));
+        });
+    }
+
+    #[test]
+    fn lines_wrap_around_scroll_area() {
+        tokio_test::block_on(async {
+            // the display height isn't a multiple of the line height
+            let mut display = panel_display(25).await;
+            let mut buffer = [0u8; 24 * 10 * 2];
+            let mut terminal = Terminal::new(&display, &FONT_6X10, &mut buffer[..]);
+
+            terminal.init(&mut display).await.unwrap();
+            terminal
+                .write_str(&mut display, "\x1b[31mA\n\x1b[32mB\n\x1b[34mC\n\x1b[33mD")
+                .await
+                .unwrap();
+
+            let (panel, _, _) = display.release();
+            let green = shown(Rgb888::new(0, 170, 0));
+            let blue = shown(Rgb888::new(0, 0, 170));
+            let brown = shown(Rgb888::new(170, 85, 0));
+
+            // the last line is at the bottom of the display
+            assert!(rows_contain(&panel, 15..25, brown));
+            assert!(!rows_contain(&panel, 0..15, brown));
+            assert!(rows_contain(&panel, 5..15, blue));
+            assert!(!rows_contain(&panel, 5..15, green));
+        });
+    }
+
+    #[test]
+    fn long_lines_are_wrapped() {
+        tokio_test::block_on(async {
+            let mut display = panel_display(30).await;
+            let mut buffer = [0u8; 24 * 10 * 2];
+            let mut terminal = Terminal::new(&display, &FONT_6X10, &mut buffer[..])
+                .with_colors(Rgb565::GREEN, Rgb565::BLACK);
+
+            terminal.init(&mut display).await.unwrap();
+            terminal.write_str(&mut display, "ABCDE").await.unwrap();
+
+            let (panel, _, _) = display.release();
+            assert!(rows_contain(&panel, 0..10, Rgb888::GREEN));
+            // the fifth character is on the second line, in the first column
+            assert!((0..6).any(|x| (10..20).any(|y| panel.pixel(x, y) == Some(Rgb888::GREEN))));
+            assert!(!(6..24).any(|x| (10..20).any(|y| panel.pixel(x, y) == Some(Rgb888::GREEN))));
+        });
+    }
+}
@@ -0,0 +1,42 @@
+//! Test doubles for host-side testing.
+//!
+//! This module is only available with the `testing` feature enabled and requires `alloc`.
+//! It provides a [`RecordingInterface`] and a [`RecordingDelay`], which record all traffic
+//! sent to the display and all requested delays. This allows golden tests for the exact
+//! command stream produced by the driver and the application.
+//!
+//! For tests of the rendered output, [`VirtualPanel`] emulates a display controller and can
+//! export the displayed image as PPM or PNG, so snapshots can be compared without hardware.
+//!
+//! # Example
+//!
+//! ```
+//! use lcd_async::{
+//!     models::ST7789,
+//!     testing::{RecordingDelay, RecordingInterface, Transfer},
+//!     Builder,
+//! };
+//!
+//! # tokio_test::block_on(async {
+//! let mut delay = RecordingDelay::new();
+//! let mut display = Builder::new(ST7789, RecordingInterface::new())
+//!     .init(&mut delay)
+//!     .await
+//!     .unwrap();
+//!
+//! display.show_raw_data(0, 0, 1, 1, &[0x12, 0x34]).await.unwrap();
+//!
+//! let (di, _, _) = display.release();
+//! assert_eq!(
+//!     di.log().last(),
+//!     Some(&Transfer::Data(vec![0x12, 0x34]))
+//! );
+//! assert!(!delay.delays().is_empty());
+//! # });
+//! ```
+
+mod panel;
+pub use panel::*;
+
+mod recording;
+pub use recording::*;
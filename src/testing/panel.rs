@@ -0,0 +1,575 @@
+use alloc::{vec, vec::Vec};
+
+use embedded_graphics_core::pixelcolor::{Rgb888, RgbColor};
+
+use crate::{
+    interface::{Interface, InterfaceKind},
+    models::Model,
+};
+
+/// An emulated MIPI DCS display controller.
+///
+/// The panel interprets the commands sent to it and writes the pixel data into an in-memory
+/// frame memory, which is sized like the controller RAM of the emulated model. The following
+/// commands are supported:
+///
+/// - Column and page address (`CASET`, `RASET`)
+/// - Memory write and continue (`RAMWR`, `RAMWRC`)
+/// - Address mode (`MADCTL`), including row/column exchange, mirroring and color order
+/// - Pixel format (`COLMOD`) with 16, 18 and 24 bits per pixel
+/// - Inversion (`INVON`, `INVOFF`)
+/// - Vertical scrolling (`VSCRDEF`, `VSCRSADD`)
+/// - Sleep and display on/off
+///
+/// All other commands are ignored. The emulated glass uses RGB subpixel order and isn't
+/// inverted, so a display which is configured correctly for a real panel might need a different
+/// [`ColorOrder`](crate::options::ColorOrder) or
+/// [`ColorInversion`](crate::options::ColorInversion) to produce the expected colors.
+///
+/// The visible area, which defaults to the whole frame memory, can be limited with
+/// [`with_visible_area`](Self::with_visible_area) to match the
+/// [`display_size`](crate::Builder::display_size) and
+/// [`display_offset`](crate::Builder::display_offset) of the display. Pixels are returned
+/// and exported in the default orientation of the glass.
+///
+/// # Examples
+///
+/// ```
+/// use embedded_graphics::pixelcolor::{Rgb565, Rgb888, RgbColor};
+/// use lcd_async::{
+///     models::ST7789,
+///     options::ColorInversion,
+///     raw_framebuf::IntoRawBytes,
+///     testing::{RecordingDelay, VirtualPanel},
+///     Builder,
+/// };
+///
+/// # tokio_test::block_on(async {
+/// let mut display = Builder::new(ST7789, VirtualPanel::for_model::<ST7789>())
+///     .display_size(240, 240)
+///     .invert_colors(ColorInversion::Normal)
+///     .init(&mut RecordingDelay::new())
+///     .await
+///     .unwrap();
+///
+/// display
+///     .show_raw_data(10, 20, 1, 1, &Rgb565::RED.into_raw_bytes())
+///     .await
+///     .unwrap();
+///
+/// let (panel, _, _) = display.release();
+/// let panel = panel.with_visible_area((0, 0), (240, 240));
+/// assert_eq!(panel.pixel(10, 20), Some(Rgb888::RED));
+///
+/// let ppm = panel.to_ppm();
+/// assert!(ppm.starts_with(b"P6\n240 240\n255\n"));
+/// # });
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VirtualPanel {
+    size: (u16, u16),
+    memory: Vec<Rgb888>,
+    visible_offset: (u16, u16),
+    visible_size: (u16, u16),
+
+    columns: (u16, u16),
+    rows: (u16, u16),
+    cursor: (u16, u16),
+    writing: bool,
+    pixel: [u8; 3],
+    pixel_len: usize,
+
+    madctl: u8,
+    bits_per_pixel: u8,
+    inverted: bool,
+    sleeping: bool,
+    display_on: bool,
+    scroll_area: (u16, u16, u16),
+    scroll_start: u16,
+}
+
+impl VirtualPanel {
+    /// Creates a new panel with a frame memory of `width` x `height` pixels.
+    ///
+    /// The frame memory is initialized to black and the panel starts in the state of a
+    /// controller after a reset: sleeping, with the display turned off.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            size: (width, height),
+            memory: vec![Rgb888::BLACK; usize::from(width) * usize::from(height)],
+            visible_offset: (0, 0),
+            visible_size: (width, height),
+            columns: (0, width.saturating_sub(1)),
+            rows: (0, height.saturating_sub(1)),
+            cursor: (0, 0),
+            writing: false,
+            pixel: [0; 3],
+            pixel_len: 0,
+            madctl: 0,
+            bits_per_pixel: 18,
+            inverted: false,
+            sleeping: true,
+            display_on: false,
+            scroll_area: (0, height, 0),
+            scroll_start: 0,
+        }
+    }
+
+    /// Creates a new panel with the frame memory size of the model `M`.
+    pub fn for_model<M: Model>() -> Self {
+        Self::new(M::FRAMEBUFFER_SIZE.0, M::FRAMEBUFFER_SIZE.1)
+    }
+
+    /// Limits the visible area of the panel to a part of the frame memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the visible area doesn't fit into the frame memory.
+    #[must_use]
+    pub fn with_visible_area(mut self, offset: (u16, u16), size: (u16, u16)) -> Self {
+        assert!(
+            u32::from(offset.0) + u32::from(size.0) <= u32::from(self.size.0)
+                && u32::from(offset.1) + u32::from(size.1) <= u32::from(self.size.1),
+            "visible area doesn't fit into the frame memory"
+        );
+
+        self.visible_offset = offset;
+        self.visible_size = size;
+        self
+    }
+
+    /// Returns the size of the visible area.
+    pub fn visible_size(&self) -> (u16, u16) {
+        self.visible_size
+    }
+
+    /// Returns the color of a pixel in the visible area as it is shown on the glass.
+    ///
+    /// Vertical scrolling, inversion and the color order are applied. Pixels are black if the
+    /// panel is sleeping or the display is turned off. Returns `None` if the coordinates are
+    /// outside the visible area.
+    pub fn pixel(&self, x: u16, y: u16) -> Option<Rgb888> {
+        if x >= self.visible_size.0 || y >= self.visible_size.1 {
+            return None;
+        }
+
+        if self.sleeping || !self.display_on {
+            return Some(Rgb888::BLACK);
+        }
+
+        let x = x + self.visible_offset.0;
+        let y = self.scrolled_row(y + self.visible_offset.1);
+        let color = self.memory[self.index(x, y)];
+
+        let (r, g, b) = if self.madctl & 0x08 != 0 {
+            (color.b(), color.g(), color.r())
+        } else {
+            (color.r(), color.g(), color.b())
+        };
+
+        Some(if self.inverted {
+            Rgb888::new(!r, !g, !b)
+        } else {
+            Rgb888::new(r, g, b)
+        })
+    }
+
+    /// Returns the color of a pixel in the frame memory, without any processing.
+    pub fn memory_pixel(&self, x: u16, y: u16) -> Option<Rgb888> {
+        (x < self.size.0 && y < self.size.1).then(|| self.memory[self.index(x, y)])
+    }
+
+    /// Exports the visible area as a binary PPM (P6) image.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (width, height) = self.visible_size;
+        let mut ppm = Vec::new();
+        ppm.extend_from_slice(alloc::format!("P6\n{} {}\n255\n", width, height).as_bytes());
+        for y in 0..height {
+            for x in 0..width {
+                ppm.extend_from_slice(&self.rgb_bytes(x, y));
+            }
+        }
+
+        ppm
+    }
+
+    /// Exports the visible area as an uncompressed PNG image.
+    pub fn to_png(&self) -> Vec<u8> {
+        let (width, height) = self.visible_size;
+
+        let mut raw = Vec::with_capacity(usize::from(height) * (usize::from(width) * 3 + 1));
+        for y in 0..height {
+            // filter type: none
+            raw.push(0);
+            for x in 0..width {
+                raw.extend_from_slice(&self.rgb_bytes(x, y));
+            }
+        }
+
+        let mut ihdr = Vec::with_capacity(13);
+        ihdr.extend_from_slice(&u32::from(width).to_be_bytes());
+        ihdr.extend_from_slice(&u32::from(height).to_be_bytes());
+        // 8 bit depth, RGB color, deflate compression, no filter, no interlace
+        ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &ihdr);
+        png_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    fn rgb_bytes(&self, x: u16, y: u16) -> [u8; 3] {
+        let color = self.pixel(x, y).unwrap_or(Rgb888::BLACK);
+        [color.r(), color.g(), color.b()]
+    }
+
+    fn index(&self, x: u16, y: u16) -> usize {
+        usize::from(y) * usize::from(self.size.0) + usize::from(x)
+    }
+
+    /// Returns the frame memory row which is shown in a row of the glass.
+    fn scrolled_row(&self, y: u16) -> u16 {
+        let (top, area, _) = self.scroll_area;
+        if y < top || y - top >= area || area == 0 {
+            return y;
+        }
+
+        let start = self.scroll_start.saturating_sub(top) % area;
+        top + (y - top + start) % area
+    }
+
+    fn handle_command(&mut self, command: u8, args: &[u8]) {
+        self.writing = false;
+        self.pixel_len = 0;
+
+        let arg = |index: usize| args.get(index).copied().unwrap_or(0);
+        let arg16 = |index: usize| u16::from_be_bytes([arg(index), arg(index + 1)]);
+
+        match command {
+            0x01 => {
+                *self = Self::new(self.size.0, self.size.1)
+                    .with_visible_area(self.visible_offset, self.visible_size)
+            }
+            0x10 => self.sleeping = true,
+            0x11 => self.sleeping = false,
+            0x20 => self.inverted = false,
+            0x21 => self.inverted = true,
+            0x28 => self.display_on = false,
+            0x29 => self.display_on = true,
+            0x2A => self.columns = (arg16(0), arg16(2)),
+            0x2B => self.rows = (arg16(0), arg16(2)),
+            0x2C => {
+                self.cursor = (self.columns.0, self.rows.0);
+                self.writing = true;
+            }
+            0x3C => self.writing = true,
+            0x33 => self.scroll_area = (arg16(0), arg16(2), arg16(4)),
+            0x36 => self.madctl = arg(0),
+            0x37 => self.scroll_start = arg16(0),
+            0x3A => {
+                self.bits_per_pixel = match arg(0) & 0x07 {
+                    0x05 => 16,
+                    0x06 => 18,
+                    _ => 24,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_data(&mut self, data: &[u8]) {
+        if !self.writing {
+            return;
+        }
+
+        for &byte in data {
+            self.pixel[self.pixel_len] = byte;
+            self.pixel_len += 1;
+
+            if self.pixel_len == self.bytes_per_pixel() {
+                self.pixel_len = 0;
+                let color = self.decode_pixel();
+                self.write_pixel(color);
+            }
+        }
+    }
+
+    fn bytes_per_pixel(&self) -> usize {
+        if self.bits_per_pixel == 16 {
+            2
+        } else {
+            3
+        }
+    }
+
+    fn decode_pixel(&self) -> Rgb888 {
+        match self.bits_per_pixel {
+            16 => {
+                let value = u16::from_be_bytes([self.pixel[0], self.pixel[1]]);
+                let r = (value >> 11) as u8 & 0x1F;
+                let g = (value >> 5) as u8 & 0x3F;
+                let b = value as u8 & 0x1F;
+                Rgb888::new(r << 3 | r >> 2, g << 2 | g >> 4, b << 3 | b >> 2)
+            }
+            18 => {
+                // the upper 6 bits of every byte are used, expanded by bit replication
+                let [r, g, b] = self.pixel.map(|byte| (byte & 0xFC) | (byte >> 6));
+                Rgb888::new(r, g, b)
+            }
+            _ => Rgb888::new(self.pixel[0], self.pixel[1], self.pixel[2]),
+        }
+    }
+
+    fn write_pixel(&mut self, color: Rgb888) {
+        let (column, row) = self.cursor;
+
+        let (mut x, mut y) = if self.madctl & 0x20 != 0 {
+            (row, column)
+        } else {
+            (column, row)
+        };
+        if self.madctl & 0x40 != 0 {
+            x = self.size.0.wrapping_sub(1).wrapping_sub(x);
+        }
+        if self.madctl & 0x80 != 0 {
+            y = self.size.1.wrapping_sub(1).wrapping_sub(y);
+        }
+
+        if x < self.size.0 && y < self.size.1 {
+            let index = self.index(x, y);
+            self.memory[index] = color;
+        }
+
+        self.cursor = if column < self.columns.1 {
+            (column + 1, row)
+        } else if row < self.rows.1 {
+            (self.columns.0, row + 1)
+        } else {
+            (self.columns.0, self.rows.0)
+        };
+    }
+}
+
+impl Interface for VirtualPanel {
+    type Word = u8;
+    type Error = core::convert::Infallible;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    async fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.handle_command(command, args);
+        Ok(())
+    }
+
+    async fn send_data_slice(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.handle_data(data);
+        Ok(())
+    }
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+/// Wraps data into a zlib stream using uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut zlib = vec![0x78, 0x01];
+
+    let mut blocks = data.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[0x01, 0x00, 0x00, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        zlib.push(u8::from(last));
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+
+    zlib.extend_from_slice(&adler32(data).to_be_bytes());
+    zlib
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    b << 16 | a
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics_core::pixelcolor::{Rgb565, Rgb666};
+
+    use super::*;
+    use crate::{
+        models::{ILI9486Rgb666, ST7789},
+        options::{ColorInversion, ColorOrder, Orientation, Rotation},
+        raw_framebuf::IntoRawBytes,
+        testing::RecordingDelay,
+        Builder,
+    };
+
+    async fn draw_corner(orientation: Orientation) -> VirtualPanel {
+        let mut display = Builder::new(ST7789, VirtualPanel::for_model::<ST7789>())
+            .orientation(orientation)
+            .invert_colors(ColorInversion::Normal)
+            .init(&mut RecordingDelay::new())
+            .await
+            .unwrap();
+
+        display
+            .show_raw_data(0, 0, 1, 1, &Rgb565::RED.into_raw_bytes())
+            .await
+            .unwrap();
+
+        display.release().0
+    }
+
+    #[test]
+    fn rgb666_pixels() {
+        tokio_test::block_on(async {
+            let mut display =
+                Builder::new(ILI9486Rgb666, VirtualPanel::for_model::<ILI9486Rgb666>())
+                    .init(&mut RecordingDelay::new())
+                    .await
+                    .unwrap();
+
+            let mut data = Rgb666::WHITE.into_raw_bytes().to_vec();
+            data.extend_from_slice(&Rgb666::RED.into_raw_bytes());
+            display.show_raw_data(0, 0, 2, 1, &data).await.unwrap();
+
+            let (panel, _, _) = display.release();
+            assert_eq!(panel.pixel(0, 0), Some(Rgb888::WHITE));
+            assert_eq!(panel.pixel(1, 0), Some(Rgb888::RED));
+        });
+    }
+
+    #[test]
+    fn orientation() {
+        tokio_test::block_on(async {
+            let cases = [
+                (Rotation::Deg0, (0, 0)),
+                (Rotation::Deg90, (239, 0)),
+                (Rotation::Deg180, (239, 319)),
+                (Rotation::Deg270, (0, 319)),
+            ];
+
+            for (rotation, (x, y)) in cases {
+                let panel = draw_corner(Orientation::new().rotate(rotation)).await;
+                assert_eq!(panel.pixel(x, y), Some(Rgb888::RED), "{rotation:?}");
+            }
+        });
+    }
+
+    #[test]
+    fn offset_and_color_order() {
+        tokio_test::block_on(async {
+            let mut display = Builder::new(ST7789, VirtualPanel::for_model::<ST7789>())
+                .display_size(100, 50)
+                .display_offset(20, 30)
+                .color_order(ColorOrder::Bgr)
+                .invert_colors(ColorInversion::Inverted)
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+
+            display
+                .show_raw_data(1, 2, 1, 1, &Rgb565::RED.into_raw_bytes())
+                .await
+                .unwrap();
+
+            let panel = display.release().0.with_visible_area((20, 30), (100, 50));
+            assert_eq!(panel.memory_pixel(21, 32), Some(Rgb888::RED));
+            // red is shown as blue due to the BGR color order and inverted to yellow
+            assert_eq!(panel.pixel(1, 2), Some(Rgb888::YELLOW));
+        });
+    }
+
+    #[test]
+    fn window_wraps_rows() {
+        tokio_test::block_on(async {
+            let mut panel = VirtualPanel::new(4, 4);
+            panel.send_command(0x11, &[]).await.unwrap();
+            panel.send_command(0x29, &[]).await.unwrap();
+            panel.send_command(0x3A, &[0x77]).await.unwrap();
+            panel.send_command(0x2A, &[0, 1, 0, 2]).await.unwrap();
+            panel.send_command(0x2B, &[0, 1, 0, 2]).await.unwrap();
+            panel.send_command(0x2C, &[]).await.unwrap();
+            panel
+                .send_data_slice(&[1, 1, 1, 2, 2, 2, 3, 3, 3])
+                .await
+                .unwrap();
+            panel.send_command(0x3C, &[]).await.unwrap();
+            panel.send_data_slice(&[4, 4, 4]).await.unwrap();
+
+            assert_eq!(panel.pixel(1, 1), Some(Rgb888::new(1, 1, 1)));
+            assert_eq!(panel.pixel(2, 1), Some(Rgb888::new(2, 2, 2)));
+            assert_eq!(panel.pixel(1, 2), Some(Rgb888::new(3, 3, 3)));
+            assert_eq!(panel.pixel(2, 2), Some(Rgb888::new(4, 4, 4)));
+            assert_eq!(panel.pixel(0, 0), Some(Rgb888::BLACK));
+        });
+    }
+
+    #[test]
+    fn vertical_scroll() {
+        tokio_test::block_on(async {
+            let mut panel = VirtualPanel::new(1, 4);
+            panel.send_command(0x11, &[]).await.unwrap();
+            panel.send_command(0x29, &[]).await.unwrap();
+            panel.send_command(0x3A, &[0x77]).await.unwrap();
+            panel.send_command(0x2C, &[]).await.unwrap();
+            panel
+                .send_data_slice(&[0, 0, 0, 1, 1, 1, 2, 2, 2, 3, 3, 3])
+                .await
+                .unwrap();
+
+            // 1 fixed line at the top, 3 line scroll area
+            panel.send_command(0x33, &[0, 1, 0, 3, 0, 0]).await.unwrap();
+            panel.send_command(0x37, &[0, 2]).await.unwrap();
+
+            let rows: Vec<u8> = (0..4).map(|y| panel.pixel(0, y).unwrap().r()).collect();
+            assert_eq!(rows, [0, 2, 3, 1]);
+        });
+    }
+
+    #[test]
+    fn export() {
+        let panel = VirtualPanel::new(2, 3);
+
+        let ppm = panel.to_ppm();
+        assert!(ppm.starts_with(b"P6\n2 3\n255\n"));
+        assert_eq!(ppm.len(), 11 + 2 * 3 * 3);
+
+        let png = panel.to_png();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(png.ends_with(&[0xAE, 0x42, 0x60, 0x82]));
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+}
@@ -0,0 +1,222 @@
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use embedded_hal_async::delay::DelayNs;
+
+use crate::interface::{Interface, InterfaceKind};
+
+/// A single transfer recorded by a [`RecordingInterface`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transfer<W> {
+    /// A command sent with [`Interface::send_command`].
+    Command {
+        /// Command opcode.
+        command: u8,
+        /// Command parameters.
+        args: Vec<u8>,
+    },
+    /// Data sent with [`Interface::send_data_slice`].
+    Data(Vec<W>),
+}
+
+/// An interface which records all transfers into an inspectable log.
+///
+/// The word type `W` selects the emulated interface: `u8` behaves like a
+/// [`Serial4Line`](InterfaceKind::Serial4Line) interface and `u16` like a
+/// [`Parallel16Bit`](InterfaceKind::Parallel16Bit) interface. Use
+/// [`RecordingInterface::new`] to create an 8 bit interface and
+/// `RecordingInterface::<u16>::default()` to create a 16 bit interface.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingInterface<W = u8> {
+    log: Vec<Transfer<W>>,
+}
+
+impl RecordingInterface {
+    /// Creates a new 8 bit interface with an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<W> RecordingInterface<W> {
+    /// Returns all recorded transfers.
+    pub fn log(&self) -> &[Transfer<W>] {
+        &self.log
+    }
+
+    /// Returns all recorded transfers and clears the log.
+    pub fn take_log(&mut self) -> Vec<Transfer<W>> {
+        core::mem::take(&mut self.log)
+    }
+
+    /// Clears the log.
+    pub fn clear(&mut self) {
+        self.log.clear();
+    }
+
+    /// Returns an iterator over the recorded commands and their parameters.
+    ///
+    /// Data transfers are skipped.
+    pub fn commands(&self) -> impl Iterator<Item = (u8, &[u8])> {
+        self.log.iter().filter_map(|transfer| match transfer {
+            &Transfer::Command {
+                command, ref args, ..
+            } => Some((command, args.as_slice())),
+            Transfer::Data(_) => None,
+        })
+    }
+
+    fn record_command(&mut self, command: u8, args: &[u8]) {
+        self.log.push(Transfer::Command {
+            command,
+            args: args.to_vec(),
+        });
+    }
+}
+
+impl<W> Default for RecordingInterface<W> {
+    fn default() -> Self {
+        Self { log: Vec::new() }
+    }
+}
+
+impl Interface for RecordingInterface<u8> {
+    type Word = u8;
+    type Error = core::convert::Infallible;
+
+    const KIND: InterfaceKind = InterfaceKind::Serial4Line;
+
+    async fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.record_command(command, args);
+        Ok(())
+    }
+
+    async fn send_data_slice(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.log.push(Transfer::Data(data.to_vec()));
+        Ok(())
+    }
+}
+
+impl Interface for RecordingInterface<u16> {
+    type Word = u16;
+    type Error = core::convert::Infallible;
+
+    const KIND: InterfaceKind = InterfaceKind::Parallel16Bit;
+
+    async fn send_command(&mut self, command: u8, args: &[u8]) -> Result<(), Self::Error> {
+        self.record_command(command, args);
+        Ok(())
+    }
+
+    async fn send_data_slice(&mut self, data: &[Self::Word]) -> Result<(), Self::Error> {
+        self.log.push(Transfer::Data(data.to_vec()));
+        Ok(())
+    }
+}
+
+/// A delay provider which records all requested delays instead of waiting.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordingDelay {
+    delays: Vec<Duration>,
+}
+
+impl RecordingDelay {
+    /// Creates a new delay provider with an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns all requested delays.
+    pub fn delays(&self) -> &[Duration] {
+        &self.delays
+    }
+
+    /// Returns the sum of all requested delays.
+    pub fn total(&self) -> Duration {
+        self.delays.iter().sum()
+    }
+
+    /// Clears the log.
+    pub fn clear(&mut self) {
+        self.delays.clear();
+    }
+}
+
+impl DelayNs for RecordingDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.delays.push(Duration::from_nanos(ns.into()));
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        self.delays.push(Duration::from_micros(us.into()));
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.delays.push(Duration::from_millis(ms.into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::{models::ST7789, Builder};
+
+    #[test]
+    fn records_init_sequence() {
+        tokio_test::block_on(async {
+            let mut delay = RecordingDelay::new();
+            let display = Builder::new(ST7789, RecordingInterface::new())
+                .init(&mut delay)
+                .await
+                .unwrap();
+
+            let (di, _, _) = display.release();
+            let commands: Vec<u8> = di.commands().map(|(command, _)| command).collect();
+
+            // software reset, sleep out, display on
+            assert_eq!(commands.first(), Some(&0x01));
+            assert!(commands.contains(&0x11));
+            assert_eq!(commands.last(), Some(&0x29));
+            assert!(delay.total() >= Duration::from_millis(120));
+        });
+    }
+
+    #[test]
+    fn records_pixel_data() {
+        tokio_test::block_on(async {
+            let mut display = Builder::new(ST7789, RecordingInterface::new())
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap();
+
+            display
+                .show_raw_data(1, 2, 1, 1, &[0xAB, 0xCD])
+                .await
+                .unwrap();
+
+            let (mut di, _, _) = display.release();
+            let log = di.take_log();
+            assert_eq!(
+                log[log.len() - 4..],
+                [
+                    Transfer::Command {
+                        command: 0x2A,
+                        args: vec![0, 1, 0, 1],
+                    },
+                    Transfer::Command {
+                        command: 0x2B,
+                        args: vec![0, 2, 0, 2],
+                    },
+                    Transfer::Command {
+                        command: 0x2C,
+                        args: vec![],
+                    },
+                    Transfer::Data(vec![0xAB, 0xCD]),
+                ]
+            );
+            assert!(di.log().is_empty());
+        });
+    }
+}
@@ -0,0 +1,278 @@
+//! Tearing-free updates synchronized to the tearing effect (TE) signal.
+
+use core::{
+    future::{poll_fn, Future},
+    pin::pin,
+    task::Poll,
+};
+
+use embedded_hal::digital::OutputPin;
+use embedded_hal_async::{delay::DelayNs, digital::Wait};
+
+use crate::{interface::Interface, models::Model, Display};
+
+/// Marker type for no tearing effect pin.
+pub struct NoTePin;
+
+/// Tearing effect pin of a [`Builder`](crate::Builder).
+///
+/// This trait is implemented for [`NoTePin`] and all pins which implement [`Wait`]. The
+/// tearing effect output of the controller is only enabled if a pin was set.
+pub trait TePin {
+    /// `true` if the tearing effect output should be enabled.
+    const ENABLED: bool;
+}
+
+impl TePin for NoTePin {
+    const ENABLED: bool = false;
+}
+
+impl<T: Wait> TePin for T {
+    const ENABLED: bool = true;
+}
+
+/// Error returned by [`Display::show_raw_data_vsync`].
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum VsyncError<DI, TE> {
+    /// Error caused by the display interface.
+    Interface(DI),
+
+    /// Error caused by the tearing effect pin's [`Wait`] implementation.
+    TePin(TE),
+
+    /// The tearing effect signal wasn't detected before the timeout elapsed.
+    ///
+    /// This can be caused by a disconnected TE pin or a display which is sleeping or
+    /// turned off.
+    Timeout,
+}
+
+impl<DI, M, RST, TE, BL> Display<DI, M, RST, TE, BL>
+where
+    DI: Interface,
+    M: Model,
+    RST: OutputPin,
+    TE: Wait,
+{
+    /// Waits for the next tearing effect signal.
+    ///
+    /// Returns [`VsyncError::Timeout`] if no rising edge was detected within `timeout_us`
+    /// microseconds.
+    pub async fn wait_for_vsync<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Result<(), VsyncError<DI::Error, TE::Error>> {
+        match with_timeout(self.te.wait_for_rising_edge(), delay, timeout_us).await {
+            Some(result) => result.map_err(VsyncError::TePin),
+            None => Err(VsyncError::Timeout),
+        }
+    }
+
+    /// Sends a raw pixel data slice to the specified rectangular region of the display,
+    /// synchronized to the display refresh.
+    ///
+    /// The address window is set before waiting for the tearing effect signal, so that the
+    /// pixel data is sent right after the signal was detected. See
+    /// [`show_raw_data`](Self::show_raw_data) for details about the pixel data.
+    ///
+    /// The scanline which generates the signal can be configured with
+    /// [`Builder::te_scanline`](crate::Builder::te_scanline).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lcd_async::{Builder, models::ST7789};
+    ///
+    /// # tokio_test::block_on(async {
+    /// # let di = lcd_async::_mock::MockDisplayInterface;
+    /// # let te = lcd_async::_mock::MockTePin;
+    /// # let mut delay = lcd_async::_mock::MockDelay;
+    /// let mut display = Builder::new(ST7789, di)
+    ///     .te_pin(te)
+    ///     .init(&mut delay)
+    ///     .await
+    ///     .unwrap();
+    ///
+    /// let frame = [0u8; 240 * 320 * 2];
+    /// // wait at most 20ms for the tearing effect signal
+    /// display
+    ///     .show_raw_data_vsync(0, 0, 240, 320, &frame, &mut delay, 20_000)
+    ///     .await
+    ///     .unwrap();
+    /// # });
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub async fn show_raw_data_vsync<DW, D>(
+        &mut self,
+        x: u16,
+        y: u16,
+        width: u16,
+        height: u16,
+        pixel_data: &[DW],
+        delay: &mut D,
+        timeout_us: u32,
+    ) -> Result<(), VsyncError<DI::Error, TE::Error>>
+    where
+        DI: Interface<Word = DW>,
+        DW: Copy,
+        D: DelayNs,
+    {
+        let ex = x + width - 1;
+        let ey = y + height - 1;
+
+        self.set_address_window(x, y, ex, ey)
+            .await
+            .map_err(VsyncError::Interface)?;
+
+        self.wait_for_vsync(delay, timeout_us).await?;
+
+        M::write_memory_start(&mut self.di)
+            .await
+            .map_err(VsyncError::Interface)?;
+        self.di
+            .send_data_slice(pixel_data)
+            .await
+            .map_err(VsyncError::Interface)?;
+        self.di.flush().await.map_err(VsyncError::Interface)
+    }
+}
+
+/// Runs a future until it completes or `timeout_us` elapses.
+///
+/// Returns `None` if the timeout elapsed first.
+async fn with_timeout<F, D>(future: F, delay: &mut D, timeout_us: u32) -> Option<F::Output>
+where
+    F: Future,
+    D: DelayNs,
+{
+    let mut future = pin!(future);
+    let mut timeout = pin!(delay.delay_us(timeout_us));
+
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+        if timeout.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal::digital::ErrorType;
+
+    use super::*;
+    use crate::{
+        models::ST7789,
+        testing::{RecordingDelay, RecordingInterface, Transfer},
+        Builder,
+    };
+
+    /// TE pin which counts the waits and optionally never fires.
+    struct TestTePin {
+        waits: usize,
+        connected: bool,
+    }
+
+    impl ErrorType for TestTePin {
+        type Error = core::convert::Infallible;
+    }
+
+    impl Wait for TestTePin {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            self.waits += 1;
+            if !self.connected {
+                core::future::pending::<()>().await;
+            }
+            Ok(())
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+    }
+
+    async fn display(
+        connected: bool,
+    ) -> Display<RecordingInterface, ST7789, crate::NoResetPin, TestTePin> {
+        Builder::new(ST7789, RecordingInterface::new())
+            .te_pin(TestTePin {
+                waits: 0,
+                connected,
+            })
+            .te_scanline(100)
+            .init(&mut RecordingDelay::new())
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn init_enables_tearing_effect() {
+        tokio_test::block_on(async {
+            let (di, _, _, _) = display(true).await.release();
+            let commands: alloc::vec::Vec<_> = di.commands().collect();
+            assert!(commands.contains(&(0x35, &[0x00][..])));
+            assert!(commands.contains(&(0x44, &[0x00, 100][..])));
+
+            // the scanline alone doesn't enable the tearing effect output
+            let (di, _, _) = Builder::new(ST7789, RecordingInterface::new())
+                .te_scanline(100)
+                .init(&mut RecordingDelay::new())
+                .await
+                .unwrap()
+                .release();
+            assert!(di
+                .commands()
+                .all(|(command, _)| command != 0x35 && command != 0x44));
+        });
+    }
+
+    #[test]
+    fn show_raw_data_vsync() {
+        tokio_test::block_on(async {
+            let mut display = display(true).await;
+            display
+                .show_raw_data_vsync(0, 0, 1, 1, &[1, 2], &mut RecordingDelay::new(), 1000)
+                .await
+                .unwrap();
+
+            let (di, _, _, te) = display.release();
+            assert_eq!(te.waits, 1);
+            assert_eq!(di.log().last(), Some(&Transfer::Data(alloc::vec![1, 2])));
+        });
+    }
+
+    #[test]
+    fn show_raw_data_vsync_timeout() {
+        tokio_test::block_on(async {
+            let mut display = display(false).await;
+            let mut delay = RecordingDelay::new();
+            let result = display
+                .show_raw_data_vsync(0, 0, 1, 1, &[1, 2], &mut delay, 1000)
+                .await;
+
+            assert!(matches!(result, Err(VsyncError::Timeout)));
+            assert_eq!(delay.delays(), [core::time::Duration::from_millis(1)]);
+
+            let (di, _, _, _) = display.release();
+            assert!(!di.log().iter().any(|t| matches!(t, Transfer::Data(_))));
+        });
+    }
+}